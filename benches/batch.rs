@@ -0,0 +1,49 @@
+use std::time::Instant;
+
+use estel_secp256k1::{batch_verify_ecdsa, hash256, PrivateKey, PublicKey, Scalar, Signature};
+
+const SIGNATURES: u8 = 64;
+const ROUNDS: u32 = 20;
+
+fn bench(name: &str, f: impl Fn() -> bool) -> u128 {
+    let start = Instant::now();
+
+    for _ in 0..ROUNDS {
+        assert!(f());
+    }
+    let ns = start.elapsed().as_nanos() / (ROUNDS * SIGNATURES as u32) as u128;
+
+    println!("{:<24} {:>8} ns/signature", name, ns);
+    ns
+}
+
+fn main() {
+    let secret = Scalar::from_bytes(&hash256(b"batch"));
+    let pvk = PrivateKey::new(secret);
+    let pk = PublicKey::from_secret(&secret);
+    let hashes: Vec<[u8; 32]> = (0..SIGNATURES).map(|i| hash256(&[i])).collect();
+    let sigs: Vec<Signature> = hashes
+        .iter()
+        .map(|z| pvk.sign(&Scalar::from_bytes(z)))
+        .collect();
+    let items: Vec<(&[u8; 32], &Signature)> = hashes.iter().zip(sigs.iter()).collect();
+
+    let single = bench("verify one by one", || {
+        items
+            .iter()
+            .all(|(z, sig)| pk.verify(&Scalar::from_bytes(z), sig))
+    });
+    let table = pk.point_table();
+    let kept = bench("verify with a kept table", || {
+        items
+            .iter()
+            .all(|(z, sig)| pk.verify_with_table(&Scalar::from_bytes(z), sig, &table))
+    });
+    let batch = bench("batch_verify_ecdsa", || batch_verify_ecdsa(&pk, &items));
+
+    println!(
+        "batch speedup: {:.2}x, {:.2}x with a kept table",
+        single as f64 / batch as f64,
+        kept as f64 / batch as f64
+    );
+}
@@ -0,0 +1,61 @@
+use std::time::Instant;
+
+use estel_secp256k1::{JacobianPt, Pt, Scalar, G, JACOBIAN_INFINITY};
+
+const ITERATIONS: u32 = 200;
+
+/// Left-to-right binary method with a separate doubling and addition
+fn mul_double_then_add(p: &Pt, k: &Scalar) -> JacobianPt {
+    let q = JacobianPt::from_affine(p);
+    let mut r = JACOBIAN_INFINITY;
+
+    for bit in k.bits() {
+        r = r.double();
+        if bit {
+            r = r.add(&q);
+        }
+    }
+    r
+}
+
+/// Left-to-right binary method with the combined step
+fn mul_dbl_add(p: &Pt, k: &Scalar) -> JacobianPt {
+    let mut r = JACOBIAN_INFINITY;
+
+    for bit in k.bits() {
+        r = r.dbl_add(p, bit);
+    }
+    r
+}
+
+fn bench(name: &str, inputs: &[Scalar], f: impl Fn(&Pt, &Scalar) -> JacobianPt) -> u128 {
+    let start = Instant::now();
+    // keep the results alive so the loop is not optimized away
+    let mut acc = 0u8;
+
+    for k in inputs {
+        acc ^= f(&G, k).x.to_bytes()[31];
+    }
+    let ns = start.elapsed().as_nanos() / inputs.len() as u128;
+
+    println!("{:<24} {:>8} ns/iter (check {:02x})", name, ns, acc);
+    ns
+}
+
+fn main() {
+    let mut k = Scalar::from_bytes(&[0x5a; 32]);
+    let inputs: Vec<Scalar> = (0..ITERATIONS)
+        .map(|_| {
+            k = k.mulmod(&k) + Scalar::ONE;
+            k
+        })
+        .collect();
+
+    let separate = bench("double then add", &inputs, mul_double_then_add);
+    let combined = bench("dbl_add", &inputs, mul_dbl_add);
+
+    println!(
+        "dbl_add speedup: {:.1}%",
+        100.0 * (separate as f64 - combined as f64) / separate as f64
+    );
+}
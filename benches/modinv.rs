@@ -0,0 +1,98 @@
+use std::time::Instant;
+
+use estel_secp256k1::{El, ModInvStrategy, Scalar};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+
+const ITERATIONS: u32 = 10_000;
+
+fn bench(name: &str, inputs: &[(Scalar, Scalar)], strategy: ModInvStrategy) {
+    let start = Instant::now();
+    // keep the results alive so the loop is not optimized away
+    let mut acc = 0u64;
+
+    for (x, m) in inputs {
+        let mut r = *x;
+        r.modinv_inner_with(m, strategy);
+        acc ^= r.d[0];
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<24} {:>8} ns/iter (check {:016x})",
+        name,
+        elapsed.as_nanos() / inputs.len() as u128,
+        acc
+    );
+}
+
+/// Same inverses with the generic big integers of num-bigint, the inverse is
+/// the Bezout coefficient of x in the extended gcd of num-integer
+fn bench_num_bigint(name: &str, inputs: &[(Scalar, Scalar)]) {
+    let inputs: Vec<(BigInt, BigInt)> = inputs
+        .iter()
+        .map(|(x, m)| {
+            (
+                BigInt::from_bytes_be(Sign::Plus, &x.to_bytes()),
+                BigInt::from_bytes_be(Sign::Plus, &m.to_bytes()),
+            )
+        })
+        .collect();
+    let start = Instant::now();
+    let mut acc = 0u64;
+
+    for (x, m) in &inputs {
+        let r = x.extended_gcd(m).x.mod_floor(m);
+        acc ^= r.iter_u64_digits().next().unwrap_or(0);
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<24} {:>8} ns/iter (check {:016x})",
+        name,
+        elapsed.as_nanos() / inputs.len() as u128,
+        acc
+    );
+}
+
+fn main() {
+    // typical field elements: x coordinates of multiples of G
+    let p = Scalar::new(
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xfffffffefffffc2f,
+    );
+    let mut x = El::from_u64(0x2a);
+    let inputs: Vec<(Scalar, Scalar)> = (0..ITERATIONS)
+        .map(|_| {
+            x = x.square() + El::from_u64(7);
+            x.reduce();
+            (x.to_scalar(), p)
+        })
+        .collect();
+
+    bench("modinv binary gcd", &inputs, ModInvStrategy::BinaryGcd);
+    bench("modinv lehmer", &inputs, ModInvStrategy::Lehmer);
+    bench(
+        "modinv euclidean",
+        &inputs,
+        ModInvStrategy::ExtendedEuclidean,
+    );
+    bench_num_bigint("num-bigint", &inputs);
+
+    // small inputs: the first quotient is about 192 bits long, which the
+    // euclidean division handles in one step
+    let mut n = 0x2au64;
+    let small: Vec<(Scalar, Scalar)> = (0..ITERATIONS)
+        .map(|_| {
+            n = n.wrapping_mul(0x5851f42d4c957f2d).wrapping_add(1) | 1;
+            (Scalar::from_u64(n), p)
+        })
+        .collect();
+
+    bench("small binary gcd", &small, ModInvStrategy::BinaryGcd);
+    bench("small lehmer", &small, ModInvStrategy::Lehmer);
+    bench("small euclidean", &small, ModInvStrategy::ExtendedEuclidean);
+    bench_num_bigint("small num-bigint", &small);
+}
@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use estel_secp256k1::El;
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench(name: &str, f: impl Fn(&El) -> El) {
+    let mut x = El::from_u64(0x2a);
+    let start = Instant::now();
+
+    // chain the results so the loop is not optimized away
+    for _ in 0..ITERATIONS {
+        x = f(&x);
+    }
+    let elapsed = start.elapsed();
+
+    x.reduce();
+    println!(
+        "{:<24} {:>8} ns/iter (check {:02x})",
+        name,
+        elapsed.as_nanos() / ITERATIONS as u128,
+        x.to_bytes()[31]
+    );
+}
+
+fn main() {
+    bench("square", |x| x.square());
+    bench("mul by itself", |x| *x * *x);
+}
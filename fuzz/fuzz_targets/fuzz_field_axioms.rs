@@ -0,0 +1,35 @@
+#![no_main]
+
+use std::convert::TryInto;
+
+use estel_secp256k1::El;
+use libfuzzer_sys::fuzz_target;
+
+// Field elements are read from 32 bytes without reduction, so the inputs also
+// cover unreduced values in [P, 2^256)
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 96 {
+        return;
+    }
+    let a = El::from_bytes(data[0..32].try_into().unwrap());
+    let b = El::from_bytes(data[32..64].try_into().unwrap());
+    let c = El::from_bytes(data[64..96].try_into().unwrap());
+
+    // a * (b + c) = a * b + a * c
+    assert_eq!(a * (b + c), a * b + a * c);
+
+    // a - a = 0
+    assert_eq!(a - a, El::ZERO);
+
+    // a + (P - a) = P = 0
+    assert_eq!(a + a.negate(1), El::ZERO);
+
+    // a * a^-1 = 1
+    let mut inv = a;
+    inv.inverse();
+    if a == El::ZERO {
+        assert_eq!(inv, El::ZERO);
+    } else {
+        assert_eq!(a * inv, El::ONE);
+    }
+});
@@ -0,0 +1,293 @@
+use crate::hmac::{hash256, sha256};
+use crate::key::{PublicKey, XOnlyPublicKey};
+
+/// Version byte of P2PKH addresses on mainnet and on testnet
+const P2PKH_MAINNET: u8 = 0x00;
+const P2PKH_TESTNET: u8 = 0x6f;
+
+/// Script opcodes of the witness versions 0 and 1, and of a 20 and 32 bytes push
+const OP_0: u8 = 0x00;
+const OP_1: u8 = 0x51;
+const OP_PUSHBYTES_20: u8 = 0x14;
+const OP_PUSHBYTES_32: u8 = 0x20;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Message word used at each of the 80 steps of the left and right lines
+const R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, //
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8, //
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, //
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2, //
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+const R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, //
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2, //
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, //
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14, //
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// Left rotation at each of the 80 steps of the left and right lines
+const S_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, //
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12, //
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, //
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, //
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+const S_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, //
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11, //
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, //
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8, //
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// Round constants of the left and right lines
+const K_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+const K_RIGHT: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// Boolean function of round j / 16
+fn ripemd_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+fn ripemd160_block(h: &mut [u32; 5], block: &[u8]) {
+    let mut x = [0u32; 16];
+    for (i, w) in x.iter_mut().enumerate() {
+        *w = u32::from_le_bytes([
+            block[4 * i],
+            block[4 * i + 1],
+            block[4 * i + 2],
+            block[4 * i + 3],
+        ]);
+    }
+
+    let [mut al, mut bl, mut cl, mut dl, mut el] = *h;
+    let [mut ar, mut br, mut cr, mut dr, mut er] = *h;
+
+    for j in 0..80 {
+        let round = j / 16;
+
+        let t = al
+            .wrapping_add(ripemd_f(round, bl, cl, dl))
+            .wrapping_add(x[R_LEFT[j]])
+            .wrapping_add(K_LEFT[round])
+            .rotate_left(S_LEFT[j])
+            .wrapping_add(el);
+        al = el;
+        el = dl;
+        dl = cl.rotate_left(10);
+        cl = bl;
+        bl = t;
+
+        // the right line uses the boolean functions in reverse order
+        let t = ar
+            .wrapping_add(ripemd_f(4 - round, br, cr, dr))
+            .wrapping_add(x[R_RIGHT[j]])
+            .wrapping_add(K_RIGHT[round])
+            .rotate_left(S_RIGHT[j])
+            .wrapping_add(er);
+        ar = er;
+        er = dr;
+        dr = cr.rotate_left(10);
+        cr = br;
+        br = t;
+    }
+
+    let t = h[1].wrapping_add(cl).wrapping_add(dr);
+    h[1] = h[2].wrapping_add(dl).wrapping_add(er);
+    h[2] = h[3].wrapping_add(el).wrapping_add(ar);
+    h[3] = h[4].wrapping_add(al).wrapping_add(br);
+    h[4] = h[0].wrapping_add(bl).wrapping_add(cr);
+    h[0] = t;
+}
+
+/// ripemd160 digest
+pub(crate) fn ripemd160(msg: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    // pad with 0x80, zeros and the length in bits, to a multiple of 64 bytes
+    let mut buf = msg.to_vec();
+    buf.push(0x80);
+    while buf.len() % 64 != 56 {
+        buf.push(0x00);
+    }
+    buf.extend_from_slice(&((msg.len() as u64) << 3).to_le_bytes());
+
+    for block in buf.chunks(64) {
+        ripemd160_block(&mut h, block);
+    }
+
+    let mut res = [0u8; 20];
+    for (i, w) in h.iter().enumerate() {
+        res[4 * i..4 * i + 4].copy_from_slice(&w.to_le_bytes());
+    }
+    res
+}
+
+/// Base58 with a 4 bytes hash256 checksum
+fn base58check(payload: &[u8]) -> String {
+    let mut buf = payload.to_vec();
+    buf.extend_from_slice(&hash256(payload)[0..4]);
+
+    // base 58 digits, least significant first
+    let mut digits: Vec<u8> = Vec::with_capacity(buf.len() * 138 / 100 + 1);
+    for byte in buf.iter() {
+        let mut carry = *byte as u32;
+
+        for d in digits.iter_mut() {
+            carry += (*d as u32) << 8;
+            *d = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // each leading zero byte is encoded as a '1'
+    let zeros = buf.iter().take_while(|b| **b == 0).count();
+    let mut res = String::with_capacity(zeros + digits.len());
+    res.extend(std::iter::repeat('1').take(zeros));
+    res.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|d| BASE58_ALPHABET[*d as usize] as char),
+    );
+
+    res
+}
+
+/// Hash of a public key in a P2PKH output, ripemd160(sha256(compressed key))
+pub fn p2pkh_hash(pk: &PublicKey) -> [u8; 20] {
+    let mut pk = *pk;
+
+    ripemd160(&sha256(&pk.serialize_sec_compressed()))
+}
+
+/// Base58Check P2PKH address of a public key, using its compressed form
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let pk = PublicKey::from_secret(&Scalar::from_u64(1));
+///
+/// assert_eq!(p2pkh_address(&pk, true), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+/// ```
+pub fn p2pkh_address(pk: &PublicKey, mainnet: bool) -> String {
+    let mut payload = [0u8; 21];
+
+    payload[0] = if mainnet {
+        P2PKH_MAINNET
+    } else {
+        P2PKH_TESTNET
+    };
+    payload[1..21].copy_from_slice(&p2pkh_hash(pk));
+
+    base58check(&payload)
+}
+
+/// SegWit v0 P2WPKH output script of a public key, OP_0 <hash160(compressed key)>
+pub fn p2wpkh_script_pubkey(pk: &PublicKey) -> [u8; 22] {
+    let mut script = [0u8; 22];
+
+    script[0] = OP_0;
+    script[1] = OP_PUSHBYTES_20;
+    script[2..22].copy_from_slice(&p2pkh_hash(pk));
+
+    script
+}
+
+/// Taproot P2TR output script, OP_1 <x-only key>
+///
+/// The key is used as is, it must already be the BIP-341 output key, tweaked
+/// with the script tree if there is one
+pub fn p2tr_script_pubkey(pk: &XOnlyPublicKey) -> [u8; 34] {
+    let mut script = [0u8; 34];
+
+    script[0] = OP_1;
+    script[1] = OP_PUSHBYTES_32;
+    script[2..34].copy_from_slice(&pk.serialize());
+
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use crate::scalar::Scalar;
+
+    #[test]
+    fn it_hashes_with_ripemd160() {
+        let vectors: [(&[u8], &str); 4] = [
+            (b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+            (b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            ),
+        ];
+
+        for (msg, expected) in vectors.iter() {
+            assert_eq!(ripemd160(msg).to_vec(), hex::decode(expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn it_encodes_p2pkh_addresses() {
+        let pk = PublicKey::from_secret(&Scalar::ONE);
+
+        assert_eq!(
+            p2pkh_hash(&pk).to_vec(),
+            hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()
+        );
+        assert_eq!(
+            p2pkh_address(&pk, true),
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+        );
+        assert_eq!(
+            p2pkh_address(&pk, false),
+            "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"
+        );
+    }
+
+    #[test]
+    fn it_builds_segwit_scripts() {
+        let pk = PublicKey::from_secret(&Scalar::ONE);
+        let xonly = XOnlyPublicKey::from_public_key(&pk);
+
+        assert_eq!(
+            p2wpkh_script_pubkey(&pk).to_vec(),
+            hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()
+        );
+        assert_eq!(
+            p2tr_script_pubkey(&xonly).to_vec(),
+            hex::decode("512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn it_encodes_base58check() {
+        assert_eq!(base58check(&[]), "3QJmnh");
+        assert_eq!(base58check(&[0x00, 0x00, 0x01]), "11BwW2qR");
+    }
+}
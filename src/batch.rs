@@ -0,0 +1,215 @@
+use crate::ecc::{Pt, B};
+use crate::field::El;
+use crate::hmac::sha256;
+use crate::jacobian::{JacobianPt, JACOBIAN_INFINITY};
+use crate::key::PublicKey;
+use crate::scalar::Scalar;
+use crate::sig::Signature;
+use crate::table::{mul2_vartime, PointTable};
+
+/// Signatures combined in a group, 2^GROUP sign combinations are tried
+const GROUP: usize = 6;
+
+/// 128 bits coefficients derived from a hash of the whole batch
+fn coefficients(pk: &PublicKey, items: &[(&[u8; 32], &Signature)]) -> Vec<Scalar> {
+    let mut pk = *pk;
+    let mut buf = Vec::with_capacity(33 + items.len() * 96);
+
+    buf.extend_from_slice(&pk.serialize_sec_compressed());
+    for (z, sig) in items {
+        buf.extend_from_slice(&z[..]);
+        buf.extend_from_slice(&sig.serialize_compact());
+    }
+    let seed = sha256(&buf);
+
+    (0..items.len() as u32)
+        .map(|i| {
+            let mut t = [0u8; 36];
+            t[0..32].copy_from_slice(&seed);
+            t[32..36].copy_from_slice(&i.to_be_bytes());
+
+            let mut a = [0u8; 32];
+            a[16..32].copy_from_slice(&sha256(&t)[0..16]);
+            let a = Scalar::from_bytes(&a);
+            if a.is_zero() {
+                Scalar::from_u64(1)
+            } else {
+                a
+            }
+        })
+        .collect()
+}
+
+/// Lift r to the point R with an even y, None if r is not a valid x
+///
+/// secp256k1 has a cofactor of 1 so any point on the curve is in the group
+fn lift_r(r: &Scalar) -> Option<Pt> {
+    let x = El::from_bytes(&r.to_bytes());
+    let (y, is_valid) = x.square().mul_add(&x, &B).sqrt();
+
+    if !is_valid {
+        return None;
+    }
+    let y = y.reduced();
+
+    Some(Pt::new(
+        x,
+        if y.is_even() {
+            y
+        } else {
+            y.negate(1).reduced()
+        },
+    ))
+}
+
+/// Invert all the s of a group with a single modular inversion
+fn invert_s(items: &[(&[u8; 32], &Signature)]) -> Vec<Scalar> {
+    // prefix[i] = s_0 * ... * s_i
+    let mut prefix = Vec::with_capacity(items.len());
+    let mut acc = Scalar::ONE;
+
+    for (_, sig) in items {
+        acc = acc.mulmod(&sig.s);
+        prefix.push(acc);
+    }
+    acc.modinv_inner();
+
+    let mut res = vec![Scalar::ZERO; items.len()];
+    for i in (0..items.len()).rev() {
+        res[i] = if i > 0 {
+            acc.mulmod(&prefix[i - 1])
+        } else {
+            acc
+        };
+        acc = acc.mulmod(&items[i].1.s);
+    }
+    res
+}
+
+/// Check a group with sum(+-a_i * R_i) == sum(a_i * u_i) * G + sum(a_i * v_i) * Q
+fn verify_group(q: &PointTable, items: &[(&[u8; 32], &Signature)], coefs: &[Scalar]) -> bool {
+    let mut ar = Vec::with_capacity(items.len());
+    let mut su = Scalar::ZERO;
+    let mut sv = Scalar::ZERO;
+
+    for (((z, sig), a), s_inv) in items.iter().zip(coefs).zip(invert_s(items)) {
+        // R is only known up to its sign from r
+        let r = match lift_r(&sig.r) {
+            Some(r) => r,
+            None => return false,
+        };
+        ar.push(PointTable::precompute(&r, 4).mul(a));
+
+        let mut zn = Scalar::from_bytes(z);
+        zn.reduce(zn.get_overflow());
+
+        let u = zn.mulmod(&s_inv);
+        su = a.mul_add(&u, &su);
+        let v = sig.r.mulmod(&s_inv);
+        sv = a.mul_add(&v, &sv);
+    }
+
+    let t = mul2_vartime(&su, &sv, q);
+    // walk the sign combinations in Gray code order, each step flips the
+    // sign of a single a_i * R_i by adding or removing 2 * a_i * R_i
+    let twice: Vec<JacobianPt> = ar.iter().map(|p| p.double()).collect();
+    let mut sum = ar.iter().fold(JACOBIAN_INFINITY, |acc, p| acc.add(p));
+
+    if sum == t {
+        return true;
+    }
+    for step in 1..1u32 << ar.len() {
+        let i = step.trailing_zeros() as usize;
+        let d = &twice[i];
+
+        // bit i of the Gray code step ^ (step >> 1) is the sign of a_i * R_i
+        if (step ^ (step >> 1)) >> i & 1 == 1 {
+            sum = sum.add(&JacobianPt { y: d.y.negate(1).reduced(), ..*d });
+        } else {
+            sum = sum.add(d);
+        }
+        if sum == t {
+            return true;
+        }
+    }
+    false
+}
+
+/// Verify ECDSA signatures of 32 bytes hashes made with the same public key
+///
+/// The signatures are checked in groups of 6 with a random linear
+/// combination of their verification equations R_i = u_i * G + v_i * Q, so
+/// that a group needs a single double scalar multiplication with G and Q.
+/// R_i is lifted from r_i with an even y, so the 64 sign combinations of a
+/// group are tried, each one costing a single addition.
+///
+/// The 128 bits coefficients are derived from a sha256 of the whole batch, as
+/// in the BIP-340 batch verification. Assuming sha256 behaves as a random
+/// oracle, a group with an invalid signature passes with a probability of
+/// about 2^-124. When a group fails, its signatures are verified one by one so
+/// the result is always the one of [`PublicKey::verify`], including for the
+/// rare signatures where r_i is R_i.x - N.
+#[must_use]
+pub fn batch_verify_ecdsa(pk: &PublicKey, items: &[(&[u8; 32], &Signature)]) -> bool {
+    // r and s must be in [1, N - 1]
+    for (_, sig) in items {
+        if sig.r.is_zero() || sig.r.get_overflow() != 0 {
+            return false;
+        }
+        if sig.s.is_zero() || sig.s.get_overflow() != 0 {
+            return false;
+        }
+    }
+
+    let coefs = coefficients(pk, items);
+    let q = pk.point_table();
+
+    items
+        .chunks(GROUP)
+        .zip(coefs.chunks(GROUP))
+        .all(|(group, a)| {
+            verify_group(&q, group, a)
+                || group
+                    .iter()
+                    .all(|(z, sig)| pk.verify_with_table(&Scalar::from_bytes(z), sig, &q))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hmac::hash256;
+    use crate::key::PrivateKey;
+
+    #[test]
+    fn it_verifies_a_batch_of_signatures() {
+        let secret = Scalar::from_bytes(&hash256(b"the force"));
+        let pvk = PrivateKey::new(secret);
+        let pk = PublicKey::from_secret(&secret);
+        let hashes: Vec<[u8; 32]> = (0..10u8).map(|i| hash256(&[i])).collect();
+        let sigs: Vec<Signature> = hashes
+            .iter()
+            .map(|z| pvk.sign(&Scalar::from_bytes(z)))
+            .collect();
+        let items: Vec<(&[u8; 32], &Signature)> = hashes.iter().zip(sigs.iter()).collect();
+
+        assert!(batch_verify_ecdsa(&pk, &items));
+        assert!(batch_verify_ecdsa(&pk, &[]));
+
+        let coefs = coefficients(&pk, &items);
+        let q = PointTable::precompute(&pk.key, 4);
+        assert!(verify_group(&q, &items[0..4], &coefs[0..4]));
+        assert!(verify_group(&q, &items[4..10], &coefs[4..10]));
+        for (s_inv, (_, sig)) in invert_s(&items).iter().zip(items.iter()) {
+            assert_eq!(s_inv.mulmod(&sig.s), Scalar::ONE);
+        }
+
+        // swapped hashes
+        let mut bad = items.clone();
+        bad[5].0 = items[6].0;
+        assert!(!batch_verify_ecdsa(&pk, &bad));
+
+        let other = PublicKey::from_secret(&Scalar::from_u64(0x2a));
+        assert!(!batch_verify_ecdsa(&other, &items));
+    }
+}
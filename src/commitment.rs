@@ -0,0 +1,60 @@
+use crate::hmac::sha256;
+use crate::key::PublicKey;
+use crate::util::ct_eq_bytes;
+
+/// Commitment to a public key for commit-reveal schemes
+///
+/// The commitment is sha256(compressed public key || nonce), the nonce must be
+/// random and kept secret until the key is revealed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Commitment(pub [u8; 32]);
+
+impl Commitment {
+    /// Commit to a public key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use estel_secp256k1::*;
+    ///
+    /// let (_, pubkey) = generate_keypair_from_seed("the force".as_bytes());
+    /// let nonce = hash256("some random nonce".as_bytes());
+    /// let c = Commitment::commit(&pubkey, &nonce);
+    ///
+    /// assert!(Commitment::open(&c, &pubkey, &nonce));
+    /// ```
+    pub fn commit(pubkey: &PublicKey, nonce: &[u8; 32]) -> Self {
+        let mut pk = *pubkey;
+        let mut buf = [0u8; 65];
+
+        buf[0..33].copy_from_slice(&pk.serialize_sec_compressed());
+        buf[33..65].copy_from_slice(nonce);
+
+        Self(sha256(&buf))
+    }
+
+    /// Check that a revealed public key and nonce match the commitment
+    #[must_use]
+    pub fn open(commitment: &Commitment, pubkey: &PublicKey, nonce: &[u8; 32]) -> bool {
+        ct_eq_bytes(&commitment.0, &Self::commit(pubkey, nonce).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::Scalar;
+
+    #[test]
+    fn it_opens_a_commitment() {
+        let pk = PublicKey::from_secret(&Scalar::from_u64(0x2a));
+        let other = PublicKey::from_secret(&Scalar::from_u64(0x2b));
+        let nonce = [0x5a; 32];
+        let c = Commitment::commit(&pk, &nonce);
+
+        assert!(Commitment::open(&c, &pk, &nonce));
+        assert!(!Commitment::open(&c, &other, &nonce));
+        assert!(!Commitment::open(&c, &pk, &[0x5b; 32]));
+        assert_ne!(c, Commitment::commit(&pk, &[0x5b; 32]));
+    }
+}
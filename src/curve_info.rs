@@ -0,0 +1,227 @@
+use crate::ecc::{B, COFACTOR, G};
+use crate::field::{El, P};
+use crate::scalar::N;
+
+/// Parameters of a short Weierstrass curve y^2 = x^3 + a * x + b over F_p
+///
+/// The generator G = (gx, gy) has order n. Numbers are 32 bytes big endian,
+/// and hexadecimal strings when serialized with serde
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CurveInfo {
+    pub p: [u8; 32],
+    pub n: [u8; 32],
+    pub a: [u8; 32],
+    pub b: [u8; 32],
+    pub gx: [u8; 32],
+    pub gy: [u8; 32],
+    pub cofactor: u64,
+}
+
+/// Parameters of secp256k1, taken from the constants used by the crate
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let info = secp256k1_info();
+///
+/// assert_eq!(info.b[31], 7);
+/// assert_eq!(info.cofactor, 1);
+/// ```
+pub fn secp256k1_info() -> CurveInfo {
+    CurveInfo {
+        p: P.to_bytes(),
+        n: N.to_bytes(),
+        a: El::ZERO.to_bytes(),
+        b: B.to_bytes(),
+        gx: G.x.to_bytes(),
+        gy: G.y.to_bytes(),
+        cofactor: COFACTOR,
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use std::convert::TryInto;
+    use std::fmt;
+
+    use ::serde::de::{self, MapAccess, SeqAccess, Visitor};
+    use ::serde::ser::SerializeStruct;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::CurveInfo;
+    use crate::hex;
+
+    const FIELDS: &[&str] = &["p", "n", "a", "b", "gx", "gy", "cofactor"];
+
+    struct Hex<'a>(&'a [u8; 32]);
+
+    impl fmt::Display for Hex<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            hex::write(f, self.0)
+        }
+    }
+
+    impl Serialize for CurveInfo {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut s = serializer.serialize_struct("CurveInfo", FIELDS.len())?;
+
+            s.serialize_field("p", &Hex(&self.p).to_string())?;
+            s.serialize_field("n", &Hex(&self.n).to_string())?;
+            s.serialize_field("a", &Hex(&self.a).to_string())?;
+            s.serialize_field("b", &Hex(&self.b).to_string())?;
+            s.serialize_field("gx", &Hex(&self.gx).to_string())?;
+            s.serialize_field("gy", &Hex(&self.gy).to_string())?;
+            s.serialize_field("cofactor", &self.cofactor)?;
+            s.end()
+        }
+    }
+
+    struct CurveInfoVisitor;
+
+    impl CurveInfoVisitor {
+        fn parse<E: de::Error>(key: &str, s: &str) -> Result<[u8; 32], E> {
+            hex::decode(s)
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or_else(|| E::custom(format!("{} must be 32 bytes in hexadecimal", key)))
+        }
+    }
+
+    impl<'de> Visitor<'de> for CurveInfoVisitor {
+        type Value = CurveInfo;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a CurveInfo struct")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<CurveInfo, A::Error> {
+            let mut nums = [[0u8; 32]; 6];
+
+            for (i, (num, field)) in nums.iter_mut().zip(FIELDS).enumerate() {
+                let s: String = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                *num = Self::parse(field, &s)?;
+            }
+            let cofactor = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(6, &self))?;
+
+            Ok(CurveInfo {
+                p: nums[0],
+                n: nums[1],
+                a: nums[2],
+                b: nums[3],
+                gx: nums[4],
+                gy: nums[5],
+                cofactor,
+            })
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<CurveInfo, A::Error> {
+            let mut nums: [Option<[u8; 32]>; 6] = [None; 6];
+            let mut cofactor = None;
+
+            while let Some(key) = map.next_key::<String>()? {
+                match FIELDS.iter().position(|f| *f == key) {
+                    Some(6) if cofactor.is_none() => cofactor = Some(map.next_value()?),
+                    Some(i) if i < 6 && nums[i].is_none() => {
+                        nums[i] = Some(Self::parse(&key, &map.next_value::<String>()?)?);
+                    }
+                    Some(_) => return Err(de::Error::custom(format!("duplicate field {}", key))),
+                    None => return Err(de::Error::unknown_field(&key, FIELDS)),
+                }
+            }
+
+            let mut nums = nums
+                .iter()
+                .zip(FIELDS)
+                .map(|(num, field)| num.ok_or_else(|| de::Error::missing_field(field)));
+            Ok(CurveInfo {
+                p: nums.next().unwrap()?,
+                n: nums.next().unwrap()?,
+                a: nums.next().unwrap()?,
+                b: nums.next().unwrap()?,
+                gx: nums.next().unwrap()?,
+                gy: nums.next().unwrap()?,
+                cofactor: cofactor.ok_or_else(|| de::Error::missing_field("cofactor"))?,
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CurveInfo {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_struct("CurveInfo", FIELDS, CurveInfoVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn it_exposes_secp256k1_parameters() {
+        // SEC 2 2.4.1
+        let info = secp256k1_info();
+        let expected = CurveInfo {
+            p: hex::decode("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+            n: hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+            a: [0u8; 32],
+            b: hex::decode("0000000000000000000000000000000000000000000000000000000000000007")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+            gx: hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+            gy: hex::decode("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+            cofactor: 1,
+        };
+
+        assert_eq!(info, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_serializes_curve_info() {
+        let info = secp256k1_info();
+        let json = serde_json::to_string(&info).unwrap();
+
+        assert!(json.starts_with(
+            "{\"p\":\"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f\","
+        ));
+        assert!(json.ends_with(",\"cofactor\":1}"));
+        assert_eq!(serde_json::from_str::<CurveInfo>(&json).unwrap(), info);
+
+        let missing = json.replace(",\"cofactor\":1", "");
+        assert!(serde_json::from_str::<CurveInfo>(&missing).is_err());
+        let short = json.replace("\"a\":\"00", "\"a\":\"");
+        assert!(serde_json::from_str::<CurveInfo>(&short).is_err());
+
+        // the fields in order, as in formats without field names
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut seq: Vec<serde_json::Value> = ["p", "n", "a", "b", "gx", "gy", "cofactor"]
+            .iter()
+            .map(|f| value[f].clone())
+            .collect();
+        let array = serde_json::Value::Array(seq.clone());
+        assert_eq!(serde_json::from_value::<CurveInfo>(array).unwrap(), info);
+
+        seq.pop();
+        assert!(serde_json::from_value::<CurveInfo>(serde_json::Value::Array(seq)).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::ecc::Pt;
+use crate::hmac::tagged_hash;
+use crate::key::PublicKey;
+use crate::rng::{random_scalar, SecureRandom};
+use crate::scalar::{negate_mod_n, Scalar};
+
+/// Non interactive proof that log_base1(result1) == log_base2(result2)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DleqProof {
+    pub c: Scalar,
+    pub r: Scalar,
+}
+
+/// c = hashDLEQ(base1 || base2 || result1 || result2 || a1 || a2) % N
+fn challenge(points: &[&Pt; 6]) -> Scalar {
+    let mut buf = [0u8; 6 * 33];
+
+    for (i, p) in points.iter().enumerate() {
+        let sec = PublicKey::from_coords(p.x, p.y).serialize_sec_compressed();
+        buf[i * 33..(i + 1) * 33].copy_from_slice(&sec);
+    }
+
+    let mut c = Scalar::from_bytes(&tagged_hash(b"DLEQ", &buf));
+    c.reduce(c.get_overflow());
+    c
+}
+
+/// Prove that secret * base1 and secret * base2 have the same discrete log
+///
+/// The proof is a Chaum-Pedersen proof made non interactive with a tagged
+/// hash of the points
+pub fn dleq_prove(
+    secret: &Scalar,
+    base1: &Pt,
+    base2: &Pt,
+    rng: &mut impl SecureRandom,
+) -> DleqProof {
+    assert!(!base1.inf && !base2.inf);
+
+    let k = random_scalar(rng);
+    let (result1, result2) = (*base1 * secret, *base2 * secret);
+    let c = challenge(&[
+        base1,
+        base2,
+        &result1,
+        &result2,
+        &(*base1 * k),
+        &(*base2 * k),
+    ]);
+
+    // r = k - c * secret
+    let r = negate_mod_n(&c).mul_add(secret, &k);
+
+    DleqProof { c, r }
+}
+
+/// Verify that result1 = x * base1 and result2 = x * base2 for the same x
+#[must_use]
+pub fn dleq_verify(proof: &DleqProof, base1: &Pt, base2: &Pt, result1: &Pt, result2: &Pt) -> bool {
+    if [base1, base2, result1, result2].iter().any(|p| p.inf) {
+        return false;
+    }
+    if proof.c.get_overflow() != 0 || proof.r.get_overflow() != 0 {
+        return false;
+    }
+
+    // a = r * base + c * result
+    let a1 = base1.mul_vartime(&proof.r) + result1.mul_vartime(&proof.c);
+    let a2 = base2.mul_vartime(&proof.r) + result2.mul_vartime(&proof.c);
+    if a1.inf || a2.inf {
+        return false;
+    }
+
+    challenge(&[base1, base2, result1, result2, &a1, &a2]) == proof.c
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::{CryptoRng, RngCore};
+
+    use super::*;
+    use crate::ecc::G;
+    use crate::hash_to_curve::hash_to_curve_tai;
+    use crate::hmac::sha256;
+
+    /// Deterministic generator for the tests, not secure
+    struct TestRng(u64);
+
+    impl RngCore for TestRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 += 1;
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(32) {
+                let h = sha256(&self.next_u64().to_be_bytes());
+                chunk.copy_from_slice(&h[..chunk.len()]);
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            RngCore::fill_bytes(self, dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for TestRng {}
+
+    #[test]
+    fn it_proves_dleq_with_the_same_base() {
+        let secret = Scalar::from_u64(0x2a);
+        let result = G * secret;
+        let proof = dleq_prove(&secret, &G, &G, &mut TestRng(0));
+
+        assert!(dleq_verify(&proof, &G, &G, &result, &result));
+        let other = G * Scalar::from_u64(0x2b);
+        assert!(!dleq_verify(&proof, &G, &G, &result, &other));
+    }
+
+    #[test]
+    fn it_proves_dleq_with_two_bases() {
+        let secret = Scalar::from_u64(0xdeadbeef);
+        let h = hash_to_curve_tai(b"dleq", b"h");
+        let (r1, r2) = (G * secret, h * secret);
+        let proof = dleq_prove(&secret, &G, &h, &mut TestRng(7));
+
+        assert!(dleq_verify(&proof, &G, &h, &r1, &r2));
+        assert!(!dleq_verify(&proof, &h, &G, &r2, &r1));
+        let bad = DleqProof { c: proof.c, r: proof.r + Scalar::from_u64(1) };
+        assert!(!dleq_verify(&bad, &G, &h, &r1, &r2));
+    }
+}
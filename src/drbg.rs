@@ -0,0 +1,102 @@
+use std::fmt;
+
+use crate::hmac::hmac256;
+
+/// HMAC-DRBG with HMAC-SHA256 from NIST SP 800-90A
+///
+/// This is the generator behind RFC 6979 nonces. There is no reseeding and no
+/// prediction resistance: the whole output is determined by the seed.
+#[derive(Clone)]
+pub struct HmacDrbg {
+    k: [u8; 32],
+    v: [u8; 32],
+}
+
+impl HmacDrbg {
+    /// Instantiate the generator with the seed entropy || nonce || personalization
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let mut drbg = Self { k: [0x00; 32], v: [0x01; 32] };
+        let mut seed = Vec::with_capacity(entropy.len() + nonce.len() + personalization.len());
+
+        seed.extend_from_slice(entropy);
+        seed.extend_from_slice(nonce);
+        seed.extend_from_slice(personalization);
+        drbg.update(&seed);
+
+        drbg
+    }
+
+    /// Fill output with pseudo random bytes
+    pub fn generate(&mut self, output: &mut [u8]) {
+        for chunk in output.chunks_mut(32) {
+            // V = HMAC_K(V)
+            self.v = hmac256(&self.k, &self.v);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[]);
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        // V || 0x00 || data
+        let mut tmp = Vec::with_capacity(33 + data.len());
+
+        tmp.extend_from_slice(&self.v);
+        tmp.push(0x00);
+        tmp.extend_from_slice(data);
+
+        // K = HMAC_K(V || 0x00 || data)
+        self.k = hmac256(&self.k, &tmp);
+        // V = HMAC_K(V)
+        self.v = hmac256(&self.k, &self.v);
+
+        if data.is_empty() {
+            return;
+        }
+
+        tmp[0..32].copy_from_slice(&self.v);
+        tmp[32] = 0x01;
+
+        // K = HMAC_K(V || 0x01 || data)
+        self.k = hmac256(&self.k, &tmp);
+        // V = HMAC_K(V)
+        self.v = hmac256(&self.k, &self.v);
+    }
+}
+
+/// The internal state is secret, it is never printed
+impl fmt::Debug for HmacDrbg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HmacDrbg(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn it_generates_bytes() {
+        let entropy = b"entropy".repeat(5);
+        let mut drbg = HmacDrbg::new(&entropy, b"nonce", b"personalization");
+        let mut out = [0u8; 40];
+
+        drbg.generate(&mut out);
+        assert_eq!(
+            out.to_vec(),
+            hex::decode(
+                "e304f76b7437c92103ef9b836b40dac36a05238bc59fa391169fa991bff7fb7b46d7a8d0858e5743"
+            )
+            .unwrap()
+        );
+
+        let mut out = [0u8; 32];
+        drbg.generate(&mut out);
+        assert_eq!(
+            out.to_vec(),
+            hex::decode("5f0b2813160b94e587d233cacb5f5a676dcf3d5426b75cd70b71e3d72e2b5c84")
+                .unwrap()
+        );
+        assert_eq!(format!("{:?}", drbg), "HmacDrbg(<redacted>)");
+    }
+}
@@ -1,29 +1,38 @@
-use std::ops::{Add, AddAssign, Mul, MulAssign};
-
-#[cfg(debug_assertions)]
+use std::convert::TryInto;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Deref, Mul, MulAssign, Neg, Sub};
 
+use crate::error::Error;
 use crate::field::El;
-use crate::scalar::Scalar;
-
-const G_X: El = El::new(
-    0x79be667ef9dcbbac,
-    0x55a06295ce870b07,
-    0x029bfcdb2dce28d9,
-    0x59f2815b16f81798,
-);
-
-const G_Y: El = El::new(
-    0x483ada7726a3c465,
-    0x5da4fbfc0e1108a8,
-    0xfd17b448a6855419,
-    0x9c47d08ffb10d4b8,
-);
+use crate::jacobian::JacobianPt;
+use crate::scalar::{Scalar, N};
+use crate::table::PointTable;
+
+const G_X: El = fe!("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+
+const G_Y: El = fe!("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
 
 pub const G: Pt = Pt::new(G_X, G_Y);
 
 pub const SECP256K1_B: u64 = 7;
 
+/// b of the curve equation as a field element
+pub const B: El = El::from_u64(SECP256K1_B);
+
+/// Check that the generator satisfies y^2 = x^3 + 7
+// Rust 1.56 does not count the call in the array length below as a use
+#[allow(dead_code)]
+const fn verify_generator() -> bool {
+    let y2 = G_Y.mul_fe(&G_Y);
+    let x3_b = G_X.mul_fe(&G_X).mul_add(&G_X, &B);
+
+    y2.equals(&x3_b)
+}
+
+// Fails to compile with a length mismatch if the generator is not on the curve
+const _: [(); 1] = [(); verify_generator() as usize];
+
 /// Represent a point on an elliptic curve with params a = 0 and b = 7
 /// x and y are the point coordinates
 /// inf indicates if it's the point at infinity
@@ -45,6 +54,50 @@ impl Pt {
         Self { x, y, inf: false }
     }
 
+    /// Check that the point satisfies the curve equation y^2 = x^3 + 7
+    ///
+    /// The point at infinity is not on the affine curve and is rejected
+    #[must_use]
+    pub fn is_on_curve(&self) -> bool {
+        if self.inf {
+            return false;
+        }
+        let y2 = self.y.square();
+
+        y2 == self.x.square().mul_add(&self.x, &B)
+    }
+
+    /// Parse a point from 64 bytes, x || y both big endian
+    ///
+    /// Returns None if the length is wrong, if a coordinate is not < P or if
+    /// the point is not on the curve
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes_unchecked(bytes).filter(|p| p.is_on_curve())
+    }
+
+    /// Parse a point from 64 bytes, x || y both big endian, without checking
+    /// that it is on the curve
+    ///
+    /// Only the length and the range of the coordinates are checked. This must
+    /// only be used with trusted input such as precomputed tables: a point off
+    /// the curve lives on a weaker curve y^2 = x^3 + b' and multiplying it with
+    /// a secret scalar leaks the scalar (invalid-curve attack). Use
+    /// [`from_bytes`](Self::from_bytes) for anything else
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 64 {
+            return None;
+        }
+        let xb: [u8; 32] = bytes[0..32].try_into().unwrap();
+        let yb: [u8; 32] = bytes[32..64].try_into().unwrap();
+        let x = El::from_bytes(&xb);
+        let y = El::from_bytes(&yb);
+
+        if x.to_bytes() != xb || y.to_bytes() != yb {
+            return None;
+        }
+        Some(Self::new(x, y))
+    }
+
     /// Elliptic curve point addition
     pub fn add_inner(&mut self, rhs: &Self) {
         if self.inf {
@@ -92,7 +145,7 @@ impl Pt {
             x3.reduce();
         }
         // y3 = s(x - x3) - y
-        y3 = s * (self.x - x3) - self.y;
+        y3 = s.mul_add(&(self.x - x3), &self.y.negate(0));
         y3.reduce();
 
         self.x = x3;
@@ -119,9 +172,87 @@ impl Pt {
         }
         *self = r;
     }
+
+    /// Multiply the point with a scalar in variable time
+    ///
+    /// This is a width 4 wNAF multiplication of `PointTable` in jacobian
+    /// coordinates. It's faster than `*` but leaks k through timing, it must
+    /// only be used with public scalars, e.g. to verify signatures. Keep a
+    /// `PointTable` to multiply the same point many times
+    pub fn mul_vartime(&self, k: &Scalar) -> Self {
+        PointTable::precompute(self, 4)
+            .mul(k)
+            .to_affine()
+            .unwrap_or(INFINITY)
+    }
+
+    /// Calculate 2^k * self with k doublings in jacobian coordinates
+    ///
+    /// There is no addition and a single inversion to get back to affine
+    /// coordinates, so this is faster than a multiplication with the scalar 2^k
+    pub fn mul_by_pow2(&self, k: u32) -> Self {
+        let mut r = JacobianPt::from_affine(self);
+
+        for _ in 0..k {
+            r = r.double();
+        }
+        r.to_affine().unwrap_or(INFINITY)
+    }
+
+    /// Multiply the point with a big endian scalar given as bytes
+    ///
+    /// This avoids the conversion to a Scalar, the bytes are not reduced % N.
+    /// It runs in variable time and must not be used with secret scalars
+    pub fn mul_bits(&self, b: &[u8; 32]) -> Self {
+        let mut r = INFINITY;
+
+        for byte in b.iter() {
+            for i in (0..8).rev() {
+                let rr = r;
+                r.add_inner(&rr);
+                if (byte >> i) & 0x1 == 0x1 {
+                    r.add_inner(self);
+                }
+            }
+        }
+        r
+    }
+}
+
+/// Cofactor h of the curve, the number of points is h * N
+pub const COFACTOR: u64 = 1;
+
+/// Multiply a point with the cofactor, clear_cofactor in RFC 9380
+///
+/// The result is in the group generated by G. For secp256k1 h = 1 and the
+/// point is returned unchanged, but points derived from arbitrary data should
+/// still go through this function so the code stays correct on a curve with
+/// h > 1
+pub fn cofactor_clear(p: JacobianPt) -> JacobianPt {
+    if COFACTOR == 1 {
+        return p;
+    }
+    p.mul_ladder(&Scalar::from_u64(COFACTOR))
+}
+
+/// Check that a point belongs to the group generated by G
+///
+/// The defense against invalid-curve attacks, where a crafted point lives on
+/// a weaker curve, is the `is_on_curve` check. secp256k1 has a cofactor of 1,
+/// so any point on the curve then satisfies n * P = O. The multiplication is
+/// still done explicitly, in jacobian coordinates and variable time since P
+/// and n are public, so the check stays correct on a curve with h > 1
+#[must_use]
+pub fn is_in_group(p: &Pt) -> bool {
+    if !p.is_on_curve() {
+        return false;
+    }
+    // n * P = (n - 1) * P + P, the table needs a scalar reduced % N
+    let r = PointTable::precompute(p, 4).mul(&(N - Scalar::from_u64(1)));
+
+    r.add(&JacobianPt::from_affine(p)).inf
 }
 
-#[cfg(debug_assertions)]
 impl fmt::Debug for Pt {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.inf {
@@ -138,25 +269,52 @@ impl Default for Pt {
     }
 }
 
+/// `+` and `-` use the jacobian formulas, with a single inversion to go
+/// back to affine coordinates
 impl Add<Pt> for Pt {
     type Output = Pt;
 
     fn add(self, rhs: Pt) -> Pt {
-        let mut r = self;
-
-        r.add_assign(&rhs);
-        r
+        &self + &rhs
     }
 }
 
-impl<'a, 'b> Add<&'a Pt> for &'b Pt {
+impl<'a> Add<&'a Pt> for &Pt {
     type Output = Pt;
 
     fn add(self, rhs: &'a Pt) -> Pt {
-        let mut r = *self;
+        JacobianPt::from_affine(self)
+            .add(&JacobianPt::from_affine(rhs))
+            .to_affine()
+            .unwrap_or(INFINITY)
+    }
+}
 
-        r.add_assign(rhs);
-        r
+impl Sub<Pt> for Pt {
+    type Output = Pt;
+
+    fn sub(self, rhs: Pt) -> Pt {
+        &self - &rhs
+    }
+}
+
+impl<'a> Sub<&'a Pt> for &Pt {
+    type Output = Pt;
+
+    fn sub(self, rhs: &'a Pt) -> Pt {
+        self + &-*rhs
+    }
+}
+
+impl Neg for Pt {
+    type Output = Pt;
+
+    fn neg(self) -> Pt {
+        if self.inf {
+            return self;
+        }
+
+        Pt::new(self.x, self.y.reduced().negate(1).reduced())
     }
 }
 
@@ -172,6 +330,10 @@ impl AddAssign<Pt> for Pt {
     }
 }
 
+/// `*` goes through the constant-time Montgomery ladder of `JacobianPt` and
+/// gets back to affine coordinates with the constant-time inversion of
+/// `El::inverse_ct`, so it's safe with secret scalars. Use `Pt::mul_vartime`
+/// or a `PointTable` for public scalars
 impl Mul<&Scalar> for Pt {
     type Output = Pt;
 
@@ -183,9 +345,28 @@ impl Mul<&Scalar> for Pt {
     }
 }
 
+impl Mul<Scalar> for Pt {
+    type Output = Pt;
+
+    fn mul(self, rhs: Scalar) -> Pt {
+        self * &rhs
+    }
+}
+
+impl Mul<Pt> for Scalar {
+    type Output = Pt;
+
+    fn mul(self, rhs: Pt) -> Pt {
+        rhs * &self
+    }
+}
+
 impl MulAssign<&Scalar> for Pt {
     fn mul_assign(&mut self, rhs: &Scalar) {
-        self.mul_scalar_inner(rhs)
+        *self = JacobianPt::from_affine(self)
+            .mul_ladder(rhs)
+            .to_affine_ct()
+            .unwrap_or(INFINITY);
     }
 }
 
@@ -202,6 +383,100 @@ impl PartialEq for Pt {
     }
 }
 
+/// Hash the normalized coordinates, consistent with `PartialEq`
+///
+/// All the points at infinity are equal, so only the flag is hashed for them
+impl Hash for Pt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inf.hash(state);
+        if !self.inf {
+            self.x.to_bytes().hash(state);
+            self.y.to_bytes().hash(state);
+        }
+    }
+}
+
+/// Point checked to be on the curve, or the point at infinity
+///
+/// The constructors reject any other point, and the arithmetic checks that
+/// its results are still on the curve in debug builds, so a bug producing an
+/// off-curve point is caught where it happens
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CurveSafePoint(Pt);
+
+impl CurveSafePoint {
+    /// Wrap a point, fails with `InvalidPoint` if it's not on the curve
+    pub fn new(p: Pt) -> Result<Self, Error> {
+        if !p.inf && !p.is_on_curve() {
+            return Err(Error::InvalidPoint);
+        }
+        Ok(Self(p))
+    }
+
+    /// Parse a point from 64 bytes x || y, see `Pt::from_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Pt::from_bytes(bytes).map(Self).ok_or(Error::InvalidPoint)
+    }
+
+    /// Wrap the result of an operation on points already on the curve
+    fn from_result(p: Pt) -> Self {
+        debug_assert!(p.inf || p.is_on_curve(), "point off the curve: {:?}", p);
+        Self(p)
+    }
+
+    /// Calculate 2 * self
+    pub fn double(&self) -> Self {
+        Self::from_result(self.0.mul_by_pow2(1))
+    }
+}
+
+impl Deref for CurveSafePoint {
+    type Target = Pt;
+
+    fn deref(&self) -> &Pt {
+        &self.0
+    }
+}
+
+impl From<CurveSafePoint> for Pt {
+    fn from(p: CurveSafePoint) -> Pt {
+        p.0
+    }
+}
+
+impl Add<CurveSafePoint> for CurveSafePoint {
+    type Output = CurveSafePoint;
+
+    fn add(self, rhs: CurveSafePoint) -> CurveSafePoint {
+        CurveSafePoint::from_result(self.0 + rhs.0)
+    }
+}
+
+impl Neg for CurveSafePoint {
+    type Output = CurveSafePoint;
+
+    fn neg(self) -> CurveSafePoint {
+        CurveSafePoint::from_result(-self.0)
+    }
+}
+
+/// Constant-time multiplication, like `*` on `Pt`
+impl Mul<&Scalar> for CurveSafePoint {
+    type Output = CurveSafePoint;
+
+    fn mul(self, rhs: &Scalar) -> CurveSafePoint {
+        CurveSafePoint::from_result(self.0 * rhs)
+    }
+}
+
+impl Mul<Scalar> for CurveSafePoint {
+    type Output = CurveSafePoint;
+
+    fn mul(self, rhs: Scalar) -> CurveSafePoint {
+        self * &rhs
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,4 +549,151 @@ mod tests {
 
         assert_eq!(p, res);
     }
+
+    #[test]
+    fn it_multiplies_by_a_power_of_two() {
+        let mut k = Scalar::ONE;
+        for _ in 0..100 {
+            k = k + k;
+        }
+
+        assert_eq!(G.mul_by_pow2(0), G);
+        assert_eq!(G.mul_by_pow2(1), G + G);
+        assert_eq!(G.mul_by_pow2(5), G * Scalar::from_u64(32));
+        assert_eq!(G.mul_by_pow2(100), G * k);
+        assert!(INFINITY.mul_by_pow2(3).inf);
+    }
+
+    #[test]
+    fn it_multiplies_a_point_with_bytes() {
+        let mut a = N;
+        a -= Scalar::from_u64(1);
+        let mut expected = G;
+        expected.mul_scalar_inner(&a);
+
+        assert_eq!(G.mul_bits(&a.to_bytes()), expected);
+        assert_eq!(G.mul_bits(&Scalar::from_u64(1).to_bytes()), G);
+        assert!(G.mul_bits(&[0u8; 32]).inf);
+    }
+
+    #[test]
+    fn it_multiplies_with_operators() {
+        let k = Scalar::from_u64(0x2a);
+        let r = Scalar::from_bytes(&[0x5a; 32]);
+        let p = G * Scalar::from_u64(7);
+
+        assert_eq!(k * G + r * p, G.mul_vartime(&k) + p.mul_vartime(&r));
+        assert_eq!(G * k, k * G);
+        assert_eq!(G * Scalar::from_u64(2), G + G);
+        assert!((G * N).inf);
+    }
+
+    #[test]
+    fn it_checks_the_endomorphism() {
+        use crate::field::BETA;
+        use crate::scalar::LAMBDA;
+
+        let p = G * LAMBDA;
+
+        assert_eq!(p, Pt::new(G.x * BETA, G.y));
+        assert_eq!(LAMBDA.mulmod(&LAMBDA).mulmod(&LAMBDA), Scalar::from_u64(1));
+        assert_eq!(BETA * BETA * BETA, El::from_u64(1));
+    }
+
+    #[test]
+    fn it_checks_point_is_on_curve() {
+        let mut p = G;
+
+        assert!(p.is_on_curve());
+        assert!(!INFINITY.is_on_curve());
+
+        p.y += El::from_u64(1);
+        assert!(!p.is_on_curve());
+    }
+
+    #[test]
+    fn it_clears_the_cofactor() {
+        let p = JacobianPt::from_affine(&(G * Scalar::from_u64(0x2a))).double();
+
+        assert_eq!(cofactor_clear(p), p);
+        assert!(cofactor_clear(JacobianPt::from_affine(&INFINITY)).inf);
+        assert!(is_in_group(&cofactor_clear(p).to_affine().unwrap()));
+    }
+
+    #[test]
+    fn it_checks_point_is_in_group() {
+        let mut p = G;
+
+        assert!(is_in_group(&p));
+        assert!(!is_in_group(&INFINITY));
+
+        p.y += El::from_u64(1);
+        assert!(!is_in_group(&p));
+    }
+
+    #[test]
+    fn it_hashes_equal_points_equally() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+
+        let hash = |p: &Pt| {
+            let mut h = DefaultHasher::new();
+            p.hash(&mut h);
+            h.finish()
+        };
+        let g2 = G * Scalar::from_u64(2);
+        // same point with unreduced coordinates
+        let lazy = Pt::new(g2.x * 2u64 - g2.x, g2.y * 2u64 - g2.y);
+        let other_inf = Pt { x: G.x, y: G.y, inf: true };
+
+        assert!(!lazy.x.is_normalized());
+        assert_eq!(lazy, g2);
+        assert_eq!(hash(&lazy), hash(&g2));
+        assert_eq!(hash(&other_inf), hash(&INFINITY));
+        assert_ne!(hash(&G), hash(&g2));
+
+        let set: HashSet<Pt> = [G, g2, lazy, INFINITY, other_inf].into_iter().collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn it_parses_points_from_bytes() {
+        let mut b = [0u8; 64];
+        b[0..32].copy_from_slice(&G.x.to_bytes());
+        b[32..64].copy_from_slice(&G.y.to_bytes());
+
+        assert_eq!(Pt::from_bytes(&b), Some(G));
+        assert_eq!(Pt::from_bytes_unchecked(&b), Some(G));
+        assert_eq!(Pt::from_bytes(&b[..63]), None);
+        assert_eq!(Pt::from_bytes_unchecked(&b[..63]), None);
+
+        // off the curve, only the unchecked variant accepts it
+        b[63] ^= 0x01;
+        assert_eq!(Pt::from_bytes(&b), None);
+        let p = Pt::from_bytes_unchecked(&b).unwrap();
+        assert!(!p.is_on_curve());
+
+        // x >= P
+        b[0..32].copy_from_slice(&[0xff; 32]);
+        assert_eq!(Pt::from_bytes_unchecked(&b), None);
+    }
+
+    #[test]
+    fn it_keeps_curve_safe_points_on_the_curve() {
+        let g = CurveSafePoint::new(G).unwrap();
+        let inf = CurveSafePoint::new(INFINITY).unwrap();
+        let off = Pt::new(G.x, G.y + El::ONE);
+
+        assert_eq!(CurveSafePoint::new(off), Err(Error::InvalidPoint));
+        assert_eq!(
+            CurveSafePoint::from_bytes(&[0u8; 64]),
+            Err(Error::InvalidPoint)
+        );
+
+        let g3 = g.double() + g;
+        assert_eq!(*g3, G * Scalar::from_u64(3));
+        assert_eq!(g * Scalar::from_u64(3), g3);
+        assert_eq!(g3 + -g3, inf);
+        assert_eq!(Pt::from(inf + g), G);
+    }
 }
@@ -3,6 +3,11 @@ use std::fmt;
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Error {
     InvalidBuffer,
+    InvalidPoint,
+    InvalidHex,
+    InvalidSignature,
+    InvalidNumber,
+    OutOfRange,
 }
 
 impl std::error::Error for Error {}
@@ -11,6 +16,11 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::InvalidBuffer => write!(f, "Invalid buffer"),
+            Error::InvalidPoint => write!(f, "Invalid point"),
+            Error::InvalidHex => write!(f, "Invalid hexadecimal string"),
+            Error::InvalidSignature => write!(f, "Invalid signature"),
+            Error::InvalidNumber => write!(f, "Invalid or empty number"),
+            Error::OutOfRange => write!(f, "Value is out of range"),
         }
     }
 }
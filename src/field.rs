@@ -1,34 +1,56 @@
 use std::cmp::Ordering;
+use std::fmt;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::str::FromStr;
 
-#[cfg(debug_assertions)]
-use std::fmt;
+use subtle::{Choice, ConditionallySelectable};
+
+use crate::error::Error;
+use crate::scalar::{parse_u256_str, Scalar};
 
-use crate::scalar::Scalar;
+pub(crate) const P: Scalar =
+    scalar!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
 
-const P: Scalar = Scalar::new(
-    0xffffffffffffffff,
-    0xffffffffffffffff,
-    0xffffffffffffffff,
-    0xfffffffefffffc2f,
-);
+/// Cube root of unity in the field, used by the secp256k1 endomorphism
+///
+/// psi(P) = (BETA * P.x, P.y) is the point LAMBDA * P, see [`LAMBDA`](crate::LAMBDA)
+pub const BETA: El = fe!("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee");
 
 /// Represent a Field Element with P = 2^256 - 2^32 - 977
-#[derive(Clone, Copy, Eq)]
+///
+/// The element is stored in 5 limbs of 52 bits (48 bits for the last one),
+/// which leaves 12 spare bits in each u64. Additions, subtractions and small
+/// multiplications are lazy: they never reduce and the carries accumulate in
+/// the spare bits. The magnitude of an element is how many times its limbs can
+/// exceed their nominal size.
+///
+/// Normalization with [`reduce`](El::reduce) is required:
+/// - before `is_zero` and `is_even`, which look at the raw limbs
+/// - before a multiplication or a square if the magnitude can exceed 8
+/// - before a subtraction if the magnitude of the right side can exceed 2
+///
+/// Comparisons and `to_bytes` normalize on their own.
+#[derive(Clone, Copy, Default, Eq)]
 pub struct El {
     d: [u64; 5],
 }
 
 impl El {
+    pub const ZERO: El = El::from_u64(0);
+    pub const ONE: El = El::from_u64(1);
+
+    /// Create a field element from its 4 limbs of 64 bits, d3 the most significant
+    ///
+    /// This is a const fn, so curve parameters can be declared as constants,
+    /// e.g. `const ONE: El = El::new(0, 0, 0, 1);`
     pub const fn new(d3: u64, d2: u64, d1: u64, d0: u64) -> Self {
         let (t0, t1, t2, t3): (u64, u64, u64, u64);
-        let t4: u64;
 
         t0 = d0 & 0x000fffffffffffff;
         t1 = d0 >> 52 | (d1 & 0x000000ffffffffff) << 12; // 12 + 40
         t2 = d1 >> 40 | (d2 & 0x000000000fffffff) << 24; // 24 + 28
         t3 = d2 >> 28 | (d3 & 0x000000000000ffff) << 36; // 36 + 16
-        t4 = d3 >> 16; // 48
+        let t4 = d3 >> 16; // 48
 
         Self { d: [t0, t1, t2, t3, t4] }
     }
@@ -37,10 +59,18 @@ impl El {
         Self::new(0, 0, 0, n)
     }
 
+    pub const fn from_u32(n: u32) -> Self {
+        Self::from_u64(n as u64)
+    }
+
+    /// Check if the element is zero, the element must be reduced
+    #[must_use]
     pub fn is_zero(&self) -> bool {
         self.d[0] | self.d[1] | self.d[2] | self.d[3] | self.d[4] == 0
     }
 
+    /// Check if the element is even, the element must be reduced
+    #[must_use]
     pub fn is_even(&self) -> bool {
         self.d[0] & 0x1 == 0
     }
@@ -106,51 +136,75 @@ impl El {
         Self { d: [d0, d1, d2, d3, d4] }
     }
 
-    /// Convert a field element to a byte array
+    /// Convert a field element to a byte array, the element is reduced first
     pub fn to_bytes(&self) -> [u8; 32] {
+        let mut n = *self;
         let mut b = [0u8; 32];
 
-        b[31] = self.d[0] as u8;
-        b[30] = (self.d[0] >> 8) as u8;
-        b[29] = (self.d[0] >> 16) as u8;
-        b[28] = (self.d[0] >> 24) as u8;
-        b[27] = (self.d[0] >> 32) as u8;
-        b[26] = (self.d[0] >> 40) as u8;
-        b[25] = (self.d[0] >> 48) as u8 | (self.d[1] << 4) as u8;
-
-        b[24] = (self.d[1] >> 4) as u8;
-        b[23] = (self.d[1] >> 12) as u8;
-        b[22] = (self.d[1] >> 20) as u8;
-        b[21] = (self.d[1] >> 28) as u8;
-        b[20] = (self.d[1] >> 36) as u8;
-        b[19] = (self.d[1] >> 44) as u8;
-
-        b[18] = self.d[2] as u8;
-        b[17] = (self.d[2] >> 8) as u8;
-        b[16] = (self.d[2] >> 16) as u8;
-        b[15] = (self.d[2] >> 24) as u8;
-        b[14] = (self.d[2] >> 32) as u8;
-        b[13] = (self.d[2] >> 40) as u8;
-        b[12] = (self.d[2] >> 48) as u8 | (self.d[3] << 4) as u8;
-
-        b[11] = (self.d[3] >> 4) as u8;
-        b[10] = (self.d[3] >> 12) as u8;
-        b[9] = (self.d[3] >> 20) as u8;
-        b[8] = (self.d[3] >> 28) as u8;
-        b[7] = (self.d[3] >> 36) as u8;
-        b[6] = (self.d[3] >> 44) as u8;
-
-        b[5] = self.d[4] as u8;
-        b[4] = (self.d[4] >> 8) as u8;
-        b[3] = (self.d[4] >> 16) as u8;
-        b[2] = (self.d[4] >> 24) as u8;
-        b[1] = (self.d[4] >> 32) as u8;
-        b[0] = (self.d[4] >> 40) as u8;
+        n.reduce();
+
+        b[31] = n.d[0] as u8;
+        b[30] = (n.d[0] >> 8) as u8;
+        b[29] = (n.d[0] >> 16) as u8;
+        b[28] = (n.d[0] >> 24) as u8;
+        b[27] = (n.d[0] >> 32) as u8;
+        b[26] = (n.d[0] >> 40) as u8;
+        b[25] = (n.d[0] >> 48) as u8 | (n.d[1] << 4) as u8;
+
+        b[24] = (n.d[1] >> 4) as u8;
+        b[23] = (n.d[1] >> 12) as u8;
+        b[22] = (n.d[1] >> 20) as u8;
+        b[21] = (n.d[1] >> 28) as u8;
+        b[20] = (n.d[1] >> 36) as u8;
+        b[19] = (n.d[1] >> 44) as u8;
+
+        b[18] = n.d[2] as u8;
+        b[17] = (n.d[2] >> 8) as u8;
+        b[16] = (n.d[2] >> 16) as u8;
+        b[15] = (n.d[2] >> 24) as u8;
+        b[14] = (n.d[2] >> 32) as u8;
+        b[13] = (n.d[2] >> 40) as u8;
+        b[12] = (n.d[2] >> 48) as u8 | (n.d[3] << 4) as u8;
+
+        b[11] = (n.d[3] >> 4) as u8;
+        b[10] = (n.d[3] >> 12) as u8;
+        b[9] = (n.d[3] >> 20) as u8;
+        b[8] = (n.d[3] >> 28) as u8;
+        b[7] = (n.d[3] >> 36) as u8;
+        b[6] = (n.d[3] >> 44) as u8;
+
+        b[5] = n.d[4] as u8;
+        b[4] = (n.d[4] >> 8) as u8;
+        b[3] = (n.d[4] >> 16) as u8;
+        b[2] = (n.d[4] >> 24) as u8;
+        b[1] = (n.d[4] >> 32) as u8;
+        b[0] = (n.d[4] >> 40) as u8;
 
         b
     }
 
+    /// Create a field element from a 256 bits little endian buffer
+    pub fn from_le_bytes(b: &[u8; 32]) -> Self {
+        let mut be = *b;
+
+        be.reverse();
+        Self::from_bytes(&be)
+    }
+
+    /// Convert a field element to a little endian byte array, the element is reduced first
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut b = self.to_bytes();
+
+        b.reverse();
+        b
+    }
+
     /// Multiply a field element with a small unsigned int
+    ///
+    /// This is the field counterpart of `Scalar::mul_small` and what `* u64`
+    /// calls, e.g. 3 * x in the jacobian formulas. The 52 bits limbs have 12
+    /// spare bits so each limb is multiplied on its own, with no carry to
+    /// propagate, and the next multiplication or `reduce` absorbs the excess
     pub fn mul_scalar_assign(&mut self, n: u64) {
         debug_assert!(n < 0x1000);
 
@@ -163,6 +217,11 @@ impl El {
 
     /// Multiply 2 field elements
     pub fn mul_fe_assign(&mut self, b: &Self) {
+        *self = self.mul_fe(b);
+    }
+
+    /// Multiply 2 field elements, usable in const contexts
+    pub const fn mul_fe(&self, b: &Self) -> Self {
         const M52: u128 = 0x000fffffffffffffu128; // 2^52 - 1
         const M48: u64 = 0x0000ffffffffffffu64; // 2^48 - 1
         const P0: u128 = 0x1000003d1u128; // 2^32 + 977
@@ -185,7 +244,6 @@ impl El {
         let mut tx: u128;
         let mut cx: u128;
         let (t0, t1, t2, mut t3, mut t4, mut t5): (u64, u64, u64, u64, u64, u128);
-        let c4: u64;
 
         // t3
         tx = a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0;
@@ -201,7 +259,7 @@ impl El {
         tx += cx * P1;
         t4 = (tx & M52) as u64;
         tx >>= 52;
-        c4 = t4 >> 48;
+        let c4 = t4 >> 48;
         t4 &= M48;
 
         // t5
@@ -242,7 +300,34 @@ impl El {
         tx += t4 as u128;
         t4 = tx as u64;
 
-        self.d = [t0, t1, t2, t3, t4];
+        Self { d: [t0, t1, t2, t3, t4] }
+    }
+
+    /// Calculate self * a + b
+    ///
+    /// The addition is lazy: the result has the magnitude of b plus one and
+    /// is not reduced
+    pub const fn mul_add(&self, a: &Self, b: &Self) -> Self {
+        let r = self.mul_fe(a);
+
+        Self {
+            d: [
+                r.d[0] + b.d[0],
+                r.d[1] + b.d[1],
+                r.d[2] + b.d[2],
+                r.d[3] + b.d[3],
+                r.d[4] + b.d[4],
+            ],
+        }
+    }
+
+    /// Reduced element as 4 limbs of 64 bits, least significant first
+    fn to_limbs64(&self) -> [u64; 4] {
+        let mut n = *self;
+
+        n.reduce();
+        let s = n.to_scalar();
+        [s.d[0], s.d[1], s.d[2], s.d[3]]
     }
 
     /// Calculate the field element square
@@ -270,7 +355,6 @@ impl El {
         let mut tx: u128;
         let mut cx: u128;
         let (t0, t1, t2, mut t3, mut t4, mut t5): (u64, u64, u64, u64, u64, u128);
-        let c4: u64;
 
         // t3
         tx = a0 * a3 * 2 + a1 * a2 * 2;
@@ -286,7 +370,7 @@ impl El {
         tx += cx * P1;
         t4 = (tx & M52) as u64;
         tx >>= 52;
-        c4 = t4 >> 48;
+        let c4 = t4 >> 48;
         t4 &= M48;
 
         // t5
@@ -330,15 +414,10 @@ impl El {
         self.d = [t0, t1, t2, t3, t4];
     }
 
-    /// Calculate the square root of the current field element
-    ///
-    /// x.sqrt() is equivalent to x^( (P + 1) / 4)
-    /// Using the bitcoin trick, the binary representation of (P + 1) / 4 is 3 groups of 1's
-    /// [1; 223], [0; 1], [22; 1], [0; 4], [2; 1], [0; 2]
+    /// Calculate the blocks of 1's shared by the exponents close to P
     ///
-    /// Using some squaring and multiplication we can do:
-    /// x^((2^n - 1).(2^m) + (2^m - 1)) = x^(2^(n+m) - 1)
-    pub fn sqrt(&self) -> (Self, bool) {
+    /// Return x^(2^N - 1) for N = 2, 3, 22 and 223
+    fn pow_blocks(&self) -> (Self, Self, Self, Self) {
         // xN = x^(2^N - 1)
 
         // x^(2 - 1)
@@ -412,6 +491,20 @@ impl El {
         }
         x223 *= &x3;
 
+        (x2, x3, x22, x223)
+    }
+
+    /// Calculate the square root of the current field element
+    ///
+    /// x.sqrt() is equivalent to x^( (P + 1) / 4)
+    /// Using the bitcoin trick, the binary representation of (P + 1) / 4 is 3 groups of 1's
+    /// [1; 223], [0; 1], [22; 1], [0; 4], [2; 1], [0; 2]
+    ///
+    /// Using some squaring and multiplication we can do:
+    /// x^((2^n - 1).(2^m) + (2^m - 1)) = x^(2^(n+m) - 1)
+    pub fn sqrt(&self) -> (Self, bool) {
+        let (x2, _, x22, x223) = self.pow_blocks();
+
         // t1 = x^(2^223 - 1) << 23
         let mut t1 = x223;
         for _ in 0..23 {
@@ -432,6 +525,67 @@ impl El {
         (r, &t1 == self)
     }
 
+    /// Calculate the Legendre symbol (x | P) = x^((P - 1) / 2)
+    ///
+    /// The binary representation of (P - 1) / 2 is
+    /// [1; 223], [0; 1], [1; 22], [0; 4], [1; 1], [0; 1], [1; 3]
+    ///
+    /// Return 1 if x is a non-zero square, -1 if x is not a square and 0 if x is 0
+    pub fn legendre(&self) -> i8 {
+        let (_, x3, x22, x223) = self.pow_blocks();
+
+        // t1 = x^(2^223 - 1) << 23
+        let mut t1 = x223;
+        for _ in 0..23 {
+            t1 = t1.square();
+        }
+        // t1 = t1 | x^(2^22 - 1) << 5
+        t1 *= &x22;
+        for _ in 0..5 {
+            t1 = t1.square();
+        }
+        // t1 = t1 | x << 4
+        t1 *= self;
+        for _ in 0..4 {
+            t1 = t1.square();
+        }
+        // t1 = t1 | x^(2^3 - 1)
+        t1 *= &x3;
+        t1.reduce();
+
+        if t1.is_zero() {
+            0
+        } else if t1 == El::from_u64(1) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Check if the field element has a square root modulo P
+    #[must_use]
+    pub fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Calculate self^exp with square and multiply, exp is 256 bits in little endian limbs
+    ///
+    /// This works for any exponent, but the addition chains of `sqrt` and
+    /// `legendre` need fewer multiplications for their fixed exponents.
+    /// It branches on the bits of exp and must not be used with a secret exponent.
+    /// The result is reduced
+    pub fn pow(&self, exp: &[u64; 4]) -> Self {
+        let mut r = El::ONE;
+
+        for i in (0..256).rev() {
+            r = r.square();
+            if (exp[i / 64] >> (i % 64)) & 0x1 == 0x1 {
+                r *= self;
+            }
+        }
+        r.reduced()
+    }
+
     /// Calculate the inverse of the field element
     /// use a modular inverse with binary gcd
     pub fn inverse(&mut self) {
@@ -441,8 +595,104 @@ impl El {
         self.from_scalar(&n);
     }
 
+    /// Calculate the inverse of the field element in constant time
+    ///
+    /// This is Fermat's little theorem x^(P - 2), the binary representation of
+    /// P - 2 is
+    /// [1; 223], [0; 1], [1; 22], [0; 4], [1; 1], [0; 1], [1; 2], [0; 1], [1; 1]
+    ///
+    /// It's slower than `inverse` but the sequence of operations doesn't depend
+    /// on the value, e.g. to get back to affine coordinates after a
+    /// multiplication with a secret scalar. 0 is mapped to 0
+    pub fn inverse_ct(&mut self) {
+        let (x2, _, x22, x223) = self.pow_blocks();
+
+        // t1 = x^(2^223 - 1) << 23
+        let mut t1 = x223;
+        for _ in 0..23 {
+            t1 = t1.square();
+        }
+        // t1 = t1 | x^(2^22 - 1) << 5
+        t1 *= &x22;
+        for _ in 0..5 {
+            t1 = t1.square();
+        }
+        // t1 = t1 | x << 3
+        t1 *= &*self;
+        for _ in 0..3 {
+            t1 = t1.square();
+        }
+        // t1 = t1 | x^(2^2 - 1) << 2
+        t1 *= &x2;
+        for _ in 0..2 {
+            t1 = t1.square();
+        }
+        // t1 = t1 | x
+        t1 *= &*self;
+
+        *self = t1.reduced();
+    }
+
+    /// Invert all the elements in place with a single inversion (Montgomery's trick)
+    ///
+    /// Zeros are left unchanged, like `inverse` which maps 0 to 0. The results
+    /// are reduced
+    pub fn invert_batch(elems: &mut [El]) {
+        if elems.is_empty() {
+            return;
+        }
+
+        // prods[i] = product of the non zero elements up to i
+        let mut prods = Vec::with_capacity(elems.len());
+        let mut acc = El::ONE;
+        for e in elems.iter_mut() {
+            e.reduce();
+            if !e.is_zero() {
+                acc *= &*e;
+            }
+            prods.push(acc);
+        }
+
+        // acc = 1 / (e_0 * ... * e_i), then 1 / e_i = acc * prods[i - 1]
+        acc.inverse();
+        for i in (0..elems.len()).rev() {
+            if elems[i].is_zero() {
+                continue;
+            }
+            let prev = if i == 0 { El::ONE } else { prods[i - 1] };
+            let inv = (acc * prev).reduced();
+
+            acc *= &elems[i];
+            elems[i] = inv;
+        }
+    }
+
+    /// Check if the field element is in its canonical form, e.g. fully reduced in [0, P)
+    #[must_use]
+    pub fn is_normalized(&self) -> bool {
+        let mut r = *self;
+
+        r.reduce();
+        r.d == self.d
+    }
+
+    /// Bring the field element into its canonical form in [0, P)
+    ///
+    /// This is the same as `reduce`: the carries are propagated and P is
+    /// subtracted once if the value is still P or more
+    pub fn normalize(&mut self) {
+        self.reduce();
+    }
+
     /// Reduce the field element by removing the carries
+    ///
+    /// The result is the canonical representation in [0, P)
     pub fn reduce(&mut self) {
+        *self = self.reduced();
+    }
+
+    /// Calculate the reduced element, usable in const contexts
+    pub const fn reduced(&self) -> Self {
         const M52: u64 = 0x000fffffffffffffu64;
         const M48: u64 = 0x0000ffffffffffffu64;
         const P0: u64 = 0x1000003d1u64;
@@ -483,7 +733,25 @@ impl El {
             d4 &= M48
         }
 
-        self.d = [d0, d1, d2, d3, d4];
+        Self { d: [d0, d1, d2, d3, d4] }
+    }
+
+    /// Check if 2 elements are equal once reduced, usable in const contexts
+    #[must_use]
+    pub const fn equals(&self, rhs: &Self) -> bool {
+        let a = self.reduced();
+        let b = rhs.reduced();
+
+        a.d[0] == b.d[0]
+            && a.d[1] == b.d[1]
+            && a.d[2] == b.d[2]
+            && a.d[3] == b.d[3]
+            && a.d[4] == b.d[4]
+    }
+
+    /// Swap a and b if flag is set, with a mask rather than a branch
+    pub(crate) fn cswap(a: &mut Self, b: &mut Self, flag: bool) {
+        El::conditional_swap(a, b, Choice::from(flag as u8));
     }
 
     /// Calculate (self - rhs) % P with overflow ofm
@@ -506,7 +774,6 @@ impl El {
     }
 }
 
-#[cfg(debug_assertions)]
 impl fmt::Debug for El {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -517,9 +784,48 @@ impl fmt::Debug for El {
     }
 }
 
-impl Default for El {
-    fn default() -> El {
-        Self { d: [0u64; 5] }
+/// 4 limbs of 64 bits, least significant first
+impl From<[u64; 4]> for El {
+    fn from(d: [u64; 4]) -> Self {
+        Self::new(d[3], d[2], d[1], d[0])
+    }
+}
+
+impl From<El> for [u64; 4] {
+    fn from(n: El) -> Self {
+        n.to_limbs64()
+    }
+}
+
+/// Read a field element from 32 bytes big endian, like `El::from_bytes`
+///
+/// Fails with `InvalidBuffer` if the slice is not 32 bytes long
+impl TryFrom<&[u8]> for El {
+    type Error = Error;
+
+    fn try_from(b: &[u8]) -> Result<Self, Error> {
+        let b: &[u8; 32] = b.try_into().or(Err(Error::InvalidBuffer))?;
+
+        Ok(El::from_bytes(b))
+    }
+}
+
+/// Parse a field element lower than P from a string
+///
+/// Strings starting with `0x` and strings with a digit in [a-f] are parsed as
+/// hexadecimal, anything else as decimal, see `Scalar::from_str`
+impl FromStr for El {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let n = parse_u256_str(s)?;
+
+        if n >= P {
+            return Err(Error::OutOfRange);
+        }
+        let mut r = El::ZERO;
+        r.from_scalar(&n);
+        Ok(r)
     }
 }
 
@@ -534,7 +840,7 @@ impl Add<El> for El {
     }
 }
 
-impl<'a, 'b> Add<&'a El> for &'b El {
+impl<'a> Add<&'a El> for &El {
     type Output = El;
 
     fn add(self, rhs: &'a El) -> El {
@@ -545,6 +851,7 @@ impl<'a, 'b> Add<&'a El> for &'b El {
     }
 }
 
+/// Lazy addition, the magnitude of the result is the sum of both magnitudes
 impl<'a> AddAssign<&'a El> for El {
     fn add_assign(&mut self, rhs: &'a El) {
         self.d[0] += rhs.d[0];
@@ -583,7 +890,7 @@ impl Mul<El> for El {
     }
 }
 
-impl<'a, 'b> Mul<&'a El> for &'b El {
+impl<'a> Mul<&'a El> for &El {
     type Output = El;
 
     fn mul(self, rhs: &'a El) -> El {
@@ -623,7 +930,7 @@ impl Sub<El> for El {
     }
 }
 
-impl<'a, 'b> Sub<&'a El> for &'b El {
+impl<'a> Sub<&'a El> for &El {
     type Output = El;
 
     fn sub(self, rhs: &'a El) -> El {
@@ -649,11 +956,16 @@ impl SubAssign<El> for El {
 
 impl Ord for El {
     fn cmp(&self, other: &Self) -> Ordering {
-        for i in (0..4).rev() {
-            if self.d[i] > other.d[i] {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.reduce();
+        b.reduce();
+        for i in (0..5).rev() {
+            if a.d[i] > b.d[i] {
                 return Ordering::Greater;
             }
-            if self.d[i] < other.d[i] {
+            if a.d[i] < b.d[i] {
                 return Ordering::Less;
             }
         }
@@ -667,18 +979,24 @@ impl PartialOrd for El {
     }
 }
 
+/// Branchless selection of the limbs with masks
+///
+/// `a.conditional_assign(&b, choice)` replaces a with b when choice is 1, and
+/// `conditional_swap` is what the ladders use to swap their points
+impl ConditionallySelectable for El {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut d = [0u64; 5];
+
+        for (i, r) in d.iter_mut().enumerate() {
+            *r = u64::conditional_select(&a.d[i], &b.d[i], choice);
+        }
+        El { d }
+    }
+}
+
 impl PartialEq for El {
     fn eq(&self, rhs: &Self) -> bool {
-        let mut a = *self;
-        let mut b = *rhs;
-
-        a.reduce();
-        b.reduce();
-        a.d[0] == b.d[0]
-            && a.d[1] == b.d[1]
-            && a.d[2] == b.d[2]
-            && a.d[3] == b.d[3]
-            && a.d[4] == b.d[4]
+        self.equals(rhs)
     }
 }
 
@@ -686,6 +1004,133 @@ impl PartialEq for El {
 mod tests {
     use super::*;
 
+    #[test]
+    fn it_creates_a_const_field_element() {
+        const P_1: El = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffffefffffc2eu64,
+        );
+        const ONE: El = El::from_u64(1);
+        const TWO: El = El::from_u32(2);
+
+        assert_eq!(P_1 + ONE, El::from_u64(0));
+        assert_eq!(P_1 + TWO, ONE);
+    }
+
+    #[test]
+    fn it_defaults_to_zero() {
+        assert_eq!(El::default(), El::ZERO);
+        assert!(El::default().is_zero());
+    }
+
+    #[test]
+    fn it_parses_field_elements() {
+        let p_1 = El::new(
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xfffffffefffffc2e,
+        );
+
+        assert_eq!("0x2a".parse::<El>().unwrap(), El::from_u64(0x2a));
+        assert_eq!("42".parse::<El>().unwrap(), El::from_u64(0x2a));
+        assert_eq!(
+            "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e"
+                .parse::<El>()
+                .unwrap(),
+            p_1
+        );
+        assert_eq!(
+            "115792089237316195423570985008687907853269984665640564039457584007908834671662"
+                .parse::<El>()
+                .unwrap(),
+            p_1
+        );
+        // 64 decimal digits, 10^63
+        assert_eq!(
+            format!("1{}", "0".repeat(63)).parse::<El>().unwrap(),
+            (0..63).fold(El::ONE, |n, _| n * El::from_u64(10))
+        );
+
+        assert_eq!("".parse::<El>().unwrap_err(), Error::InvalidNumber);
+        assert_eq!("0x2g".parse::<El>().unwrap_err(), Error::InvalidNumber);
+        assert_eq!(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+                .parse::<El>()
+                .unwrap_err(),
+            Error::OutOfRange
+        );
+    }
+
+    #[test]
+    fn it_inverts_a_batch() {
+        let mut elems = [
+            El::from_u64(3),
+            El::ZERO,
+            El::from_u64(0x2a) * 5u64,
+            El::ONE,
+            El::new(
+                0xffffffffffffffff,
+                0xffffffffffffffff,
+                0xffffffffffffffff,
+                0xfffffffefffffc2e,
+            ),
+            El::ZERO,
+        ];
+        let expected: Vec<El> = elems
+            .iter()
+            .map(|e| {
+                let mut inv = *e;
+                inv.inverse();
+                inv
+            })
+            .collect();
+
+        El::invert_batch(&mut elems);
+        assert_eq!(elems.to_vec(), expected);
+        assert!(elems.iter().all(|e| e.is_normalized()));
+        assert!(elems[1].is_zero() && elems[5].is_zero());
+
+        let mut zeros = [El::ZERO; 2];
+        El::invert_batch(&mut zeros);
+        assert_eq!(zeros, [El::ZERO; 2]);
+        El::invert_batch(&mut []);
+    }
+
+    #[test]
+    fn it_converts_u64_limbs() {
+        let d = [0x1, 0x2, 0x3, 0x4];
+        let a = El::from(d);
+
+        assert_eq!(a, El::new(0x4, 0x3, 0x2, 0x1));
+        assert_eq!(<[u64; 4]>::from(a), d);
+        assert_eq!(<[u64; 4]>::from(a + a), [0x2, 0x4, 0x6, 0x8]);
+    }
+
+    #[test]
+    fn it_converts_little_endian_bytes() {
+        let mut b = [0u8; 32];
+        b[0] = 0x2f;
+        b[1] = 0xfc;
+
+        let a = El::from_le_bytes(&b);
+        assert_eq!(a, El::from_u64(0xfc2f));
+        assert_eq!(a.to_le_bytes(), b);
+
+        // p - 1 + 2 is reduced to 1
+        let p_1 = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffffefffffc2eu64,
+        );
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        assert_eq!((p_1 + El::from_u64(2)).to_le_bytes(), one);
+    }
+
     #[test]
     fn it_adds_a_field_element() {
         let a = El::new(
@@ -710,6 +1155,31 @@ mod tests {
         assert_eq!(r2, expected);
     }
 
+    #[test]
+    fn it_accumulates_additions_before_reducing() {
+        let a = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffffefffffc2eu64,
+        );
+        let mut r = El::default();
+
+        for _ in 0..1000 {
+            r += a;
+        }
+        r.reduce();
+
+        // r = ((p - 1) * 1000) % p = p - 1000
+        let expected = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffffefffff847u64,
+        );
+        assert_eq!(r.d, expected.d);
+    }
+
     #[test]
     fn it_mult_a_field_element() {
         // A = p - 2^42
@@ -764,6 +1234,34 @@ mod tests {
         assert_eq!(r, expected);
     }
 
+    #[test]
+    fn it_mul_adds_field_elements() {
+        // a = p - 1, b = p - 2, c = 3
+        let a = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffffefffffc2eu64,
+        );
+        let b = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffffefffffc2du64,
+        );
+        let c = El::from_u64(3);
+        let mut r = a.mul_add(&b, &c);
+
+        r.reduce();
+
+        // r = (-1 * -2 + 3) % p = 5
+        assert_eq!(r, El::from_u64(5));
+
+        let mut expected = a * b + c;
+        expected.reduce();
+        assert_eq!(r, expected);
+    }
+
     #[test]
     fn it_square_a_field_element() {
         // A = p - 1000
@@ -786,6 +1284,23 @@ mod tests {
         assert_eq!(a, expected);
     }
 
+    #[test]
+    fn it_squares_like_a_multiplication() {
+        let mut a = El::from_u64(0x2a);
+
+        for _ in 0..100 {
+            let mut r = a.square();
+            let mut expected = a * a;
+
+            r.reduce();
+            expected.reduce();
+            assert_eq!(r, expected);
+
+            // lazy additions raise the magnitude of the next input
+            a = r * a + El::from_u64(7) + a;
+        }
+    }
+
     #[test]
     fn it_mult_a_scalar() {
         // A = p - 2^42
@@ -808,6 +1323,23 @@ mod tests {
         assert_eq!(r, expected);
     }
 
+    #[test]
+    fn it_assigns_and_swaps_conditionally() {
+        let (a, b) = (El::from_u64(0x2a), El::from_u64(0x2b));
+
+        let mut r = a;
+        r.conditional_assign(&b, Choice::from(0));
+        assert_eq!(r, a);
+        r.conditional_assign(&b, Choice::from(1));
+        assert_eq!(r, b);
+
+        let (mut x, mut y) = (a, b);
+        El::cswap(&mut x, &mut y, false);
+        assert_eq!((x, y), (a, b));
+        El::cswap(&mut x, &mut y, true);
+        assert_eq!((x, y), (b, a));
+    }
+
     #[test]
     fn it_tests_equality() {
         // A=0xfffffffffffffffffffffffffffffffffffffffffffffffffffffbfefffffc2f = p - 2^42
@@ -850,6 +1382,79 @@ mod tests {
         assert!(b >= a);
     }
 
+    #[test]
+    fn it_tests_legendre_symbol() {
+        // 3 and 7 are not quadratic residues % p
+        assert_eq!(El::from_u64(0).legendre(), 0);
+        assert_eq!(El::from_u64(2).legendre(), 1);
+        assert_eq!(El::from_u64(3).legendre(), -1);
+        assert_eq!(El::from_u64(7).legendre(), -1);
+
+        let a = El::new(
+            0x9075b4ee4d4788ca,
+            0xbb49f7f81c221151,
+            0xfa2f68914d0aa833,
+            0x388fa11ff621a970,
+        );
+        let a2 = a.square();
+        let (_, is_valid) = a2.sqrt();
+
+        assert!(a2.is_square());
+        assert!(is_valid);
+        assert!(!(a2 * 0x3u64).is_square());
+    }
+
+    #[test]
+    fn it_normalizes_a_field_element() {
+        let p = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffffefffffc2fu64,
+        );
+        let mut a = p + El::from_u64(2);
+
+        assert!(!a.is_normalized());
+        assert_eq!(a.to_bytes(), El::from_u64(2).to_bytes());
+        assert!(a > El::from_u64(1));
+        assert!(a < El::from_u64(3));
+
+        a.normalize();
+        assert!(a.is_normalized());
+        assert_eq!(a.d, El::from_u64(2).d);
+    }
+
+    #[test]
+    fn it_raises_to_a_power() {
+        let x = El::from_u64(0x2a);
+        // P - 2 and (P + 1) / 4, in little endian limbs
+        let p_2 = [
+            0xfffffffefffffc2d,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+        ];
+        let p_1_4 = [
+            0xffffffffbfffff0c,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0x3fffffffffffffff,
+        ];
+        let mut x_inv = x;
+        x_inv.inverse();
+        let mut x_2_64 = x;
+        for _ in 0..64 {
+            x_2_64 = x_2_64.square();
+        }
+
+        assert_eq!(x.pow(&[0; 4]), El::ONE);
+        assert_eq!(x.pow(&[1, 0, 0, 0]), x);
+        assert_eq!(x.pow(&[3, 0, 0, 0]), El::from_u64(0x2a * 0x2a * 0x2a));
+        assert_eq!(x.pow(&[0, 1, 0, 0]), x_2_64.reduced());
+        assert_eq!(x.pow(&p_2), x_inv);
+        assert_eq!(x.square().pow(&p_1_4), x.square().sqrt().0.reduced());
+    }
+
     #[test]
     fn it_tests_inverse() {
         // a=0xfffffffffffffffffffffffffffffffffffffffffffffffffffffbfefffffc2f = p - 2^42
@@ -904,4 +1509,42 @@ mod tests {
         c.inverse();
         assert_eq!(c, r);
     }
+
+    #[test]
+    fn it_inverts_in_constant_time() {
+        let b = El::new(
+            0x9075b4ee4d4788ca,
+            0xbb49f7f81c221151,
+            0xfa2f68914d0aa833,
+            0x388fa11ff621a970,
+        );
+        // p - 2^42
+        let a = El::new(
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xffffffffffffffffu64,
+            0xfffffbfefffffc2fu64,
+        );
+
+        for x in [b, a, El::ONE].iter() {
+            let mut x_inv = *x;
+            x_inv.inverse_ct();
+            let mut expected = *x;
+            expected.inverse();
+            assert_eq!(x_inv, expected);
+        }
+
+        let mut zero = El::ZERO;
+        zero.inverse_ct();
+        assert!(zero.is_zero());
+    }
+
+    #[test]
+    fn it_converts_from_byte_slices() {
+        let b = BETA.to_bytes();
+
+        assert_eq!(El::try_from(&b[..]), Ok(BETA));
+        assert_eq!(El::try_from(&b[1..]), Err(Error::InvalidBuffer));
+        assert_eq!(El::try_from(&[0u8; 33][..]), Err(Error::InvalidBuffer));
+    }
 }
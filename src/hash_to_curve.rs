@@ -0,0 +1,340 @@
+use crate::ecc::{cofactor_clear, Pt, INFINITY};
+use crate::field::El;
+use crate::hmac::sha256;
+use crate::jacobian::JacobianPt;
+use crate::key::XOnlyPublicKey;
+
+/// A' of the curve E': y^2 = x^3 + A' * x + B', 3-isogenous to secp256k1
+const ISO_A: El = fe!("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533");
+
+/// Bytes per field element in hash_to_field, ceil((ceil(log2(P)) + 128) / 8)
+const HASH_TO_FIELD_L: usize = 48;
+
+/// B' of E'
+const ISO_B: El = El::from_u64(1771);
+
+/// Z = -11, the non-square of the simplified SWU map for E'
+const SSWU_Z: El = fe!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc24");
+
+/// Coefficients of the isogeny polynomials, least significant first (RFC 9380 E.1)
+const ISO_X_NUM: [El; 4] = [
+    fe!("8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7"),
+    fe!("07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581"),
+    fe!("534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262"),
+    fe!("8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c"),
+];
+
+/// Monic denominator of x
+const ISO_X_DEN: [El; 3] = [
+    fe!("d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b"),
+    fe!("edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14"),
+    El::from_u64(1),
+];
+
+/// Numerator of y
+const ISO_Y_NUM: [El; 4] = [
+    fe!("4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c"),
+    fe!("c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3"),
+    fe!("29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931"),
+    fe!("2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84"),
+];
+
+/// Monic denominator of y
+const ISO_Y_DEN: [El; 4] = [
+    fe!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b"),
+    fe!("7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573"),
+    fe!("6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f"),
+    El::from_u64(1),
+];
+
+/// Hash a message to a curve point with the try and increment method
+///
+/// The candidate x coordinate is sha256(prefix || msg || ctr || 0x00) for a
+/// one byte counter starting at 0, the first x on the curve is lifted to the
+/// point with an even y. This is the encoding used by the ECVRF TAI suites.
+/// It runs in variable time and must not be used with secret messages
+pub fn hash_to_curve_tai(prefix: &[u8], msg: &[u8]) -> Pt {
+    let mut buf = Vec::with_capacity(prefix.len() + msg.len() + 2);
+
+    buf.extend_from_slice(prefix);
+    buf.extend_from_slice(msg);
+    buf.extend_from_slice(&[0x00, 0x00]);
+    let ctr = buf.len() - 2;
+
+    for i in 0..=255u8 {
+        buf[ctr] = i;
+        if let Ok(pk) = XOnlyPublicKey::parse(&sha256(&buf)) {
+            return cofactor_clear(JacobianPt::from_affine(&pk.key))
+                .to_affine()
+                .unwrap_or(INFINITY);
+        }
+    }
+    // each candidate fails with probability ~1/2
+    unreachable!("no point found after 256 candidates")
+}
+
+/// Expand msg to len_in_bytes uniform bytes with sha256 (RFC 9380 5.3.1)
+///
+/// A dst longer than 255 bytes is replaced by sha256("H2C-OVERSIZE-DST-" || dst)
+///
+/// # Panics
+///
+/// Panics if len_in_bytes is 0 or greater than 255 * 32
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    assert!(len_in_bytes > 0 && len_in_bytes <= 255 * 32);
+
+    let long_dst;
+    let dst = if dst.len() > 255 {
+        long_dst = sha256(&[b"H2C-OVERSIZE-DST-", dst].concat());
+        &long_dst[..]
+    } else {
+        dst
+    };
+    // DST' = dst || len(dst)
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    // b_0 = H(Z_pad || msg || l_i_b_str || 0x00 || DST')
+    let mut buf = Vec::with_capacity(64 + msg.len() + 3 + dst_prime.len());
+    buf.extend_from_slice(&[0u8; 64]);
+    buf.extend_from_slice(msg);
+    buf.extend_from_slice(&(len_in_bytes as u16).to_be_bytes());
+    buf.push(0x00);
+    buf.extend_from_slice(&dst_prime);
+    let b0 = sha256(&buf);
+
+    // b_1 = H(b_0 || 0x01 || DST'), b_i = H((b_0 ^ b_(i - 1)) || i || DST')
+    let mut res = Vec::with_capacity(len_in_bytes + 31);
+    let mut bi = [0u8; 32];
+    let mut i = 1u8;
+    while res.len() < len_in_bytes {
+        let mut buf = Vec::with_capacity(33 + dst_prime.len());
+        buf.extend(b0.iter().zip(bi.iter()).map(|(a, b)| a ^ b));
+        buf.push(i);
+        buf.extend_from_slice(&dst_prime);
+        bi = sha256(&buf);
+        res.extend_from_slice(&bi);
+        i = i.wrapping_add(1);
+    }
+    res.truncate(len_in_bytes);
+
+    res
+}
+
+/// Hash a message to 2 field elements with expand_message_xmd (RFC 9380 5.2)
+///
+/// Each element is read from 48 bytes, so that its bias mod P is negligible
+pub fn hash_to_field(msg: &[u8], dst: &[u8]) -> [El; 2] {
+    let uniform = expand_message_xmd(msg, dst, 2 * HASH_TO_FIELD_L);
+    let mut res = [El::ZERO; 2];
+
+    for (u, bin) in res.iter_mut().zip(uniform.chunks(HASH_TO_FIELD_L)) {
+        // u = hi * 2^256 + lo, with 2^256 % P = 2^32 + 977
+        let mut hi = [0u8; 32];
+        hi[16..32].copy_from_slice(&bin[0..16]);
+        let lo: &[u8; 32] = bin[16..48].try_into().unwrap();
+
+        *u = El::from_bytes(&hi)
+            .mul_add(&El::from_u64(0x1000003d1), &El::from_bytes(lo))
+            .reduced();
+    }
+    res
+}
+
+/// Hash a message to a curve point, this is the secp256k1_XMD:SHA-256_SSWU_RO_
+/// suite of RFC 9380
+///
+/// Both field elements of `hash_to_field` are mapped with the simplified SWU
+/// map and the 3-isogeny, the points are added and the cofactor is cleared.
+/// dst is the domain separation tag of the application. It runs in variable
+/// time and must not be used with secret messages
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Pt {
+    let [u0, u1] = hash_to_field(msg, dst);
+    let q0 = iso_map(&simplified_swu_map(&u0));
+    let q1 = iso_map(&simplified_swu_map(&u1));
+
+    cofactor_clear(JacobianPt::from_affine(&(q0 + q1)))
+        .to_affine()
+        .unwrap_or(INFINITY)
+}
+
+/// Evaluate the polynomial c[0] + c[1] * x + ... with Horner's method
+fn eval_poly(c: &[El], x: &El) -> El {
+    let (last, rest) = c.split_last().unwrap();
+
+    rest.iter()
+        .rev()
+        .fold(*last, |r, ci| r.mul_add(x, ci))
+        .reduced()
+}
+
+/// g(x) = x^3 + A' * x + B'
+fn iso_curve_rhs(x: &El) -> El {
+    (x.square() + ISO_A).mul_add(x, &ISO_B).reduced()
+}
+
+/// Map a field element to a point of E' with the simplified SWU map (RFC 9380 6.6.2)
+///
+/// The returned point is on E', not secp256k1, use `iso_map` to get a secp256k1
+/// point. It runs in variable time and must not be used with secret inputs
+pub fn simplified_swu_map(u: &El) -> Pt {
+    let u = u.reduced();
+    let zu2 = (SSWU_Z * u.square()).reduced();
+    let mut tv1 = (zu2.square() + zu2).reduced();
+
+    // x1 = -B' / A' * (1 + 1 / (Z^2 * u^4 + Z * u^2)), or B' / (Z * A') if not invertible
+    let x1 = if tv1.is_zero() {
+        let mut za = SSWU_Z * ISO_A;
+        za.inverse();
+        (ISO_B * za).reduced()
+    } else {
+        let mut a = ISO_A;
+        a.inverse();
+        tv1.inverse();
+        (ISO_B.negate(1) * a * (tv1 + El::from_u64(1))).reduced()
+    };
+
+    let (x, mut y) = match iso_curve_rhs(&x1).sqrt() {
+        (y, true) => (x1, y),
+        _ => {
+            // g(x2) = Z^3 * u^6 * g(x1) is a square when g(x1) isn't
+            let x2 = (zu2 * x1).reduced();
+            (x2, iso_curve_rhs(&x2).sqrt().0)
+        }
+    };
+    y.reduce();
+    // sgn0(y) = sgn0(u)
+    if y.is_even() != u.is_even() {
+        y = y.negate(1).reduced();
+    }
+
+    Pt::new(x, y)
+}
+
+/// Map a point of E' to secp256k1 with the 3-isogeny of RFC 9380 appendix E.1
+///
+/// The exceptional points where a denominator is zero are mapped to infinity
+pub fn iso_map(p: &Pt) -> Pt {
+    if p.inf {
+        return INFINITY;
+    }
+
+    let mut x_den = eval_poly(&ISO_X_DEN, &p.x);
+    let mut y_den = eval_poly(&ISO_Y_DEN, &p.x);
+    if x_den.is_zero() || y_den.is_zero() {
+        return INFINITY;
+    }
+    x_den.inverse();
+    y_den.inverse();
+
+    let x = (eval_poly(&ISO_X_NUM, &p.x) * x_den).reduced();
+    let y = (p.y * eval_poly(&ISO_Y_NUM, &p.x) * y_den).reduced();
+
+    Pt::new(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use super::*;
+    use crate::ecc::is_in_group;
+    use crate::hex;
+
+    #[test]
+    fn it_hashes_to_curve() {
+        let p1 = hash_to_curve_tai(b"prefix", b"msg");
+        let p2 = hash_to_curve_tai(b"prefix", b"msg");
+        let p3 = hash_to_curve_tai(b"prefix", b"msg2");
+        let mut y = p1.y;
+
+        y.reduce();
+        assert!(is_in_group(&p1));
+        assert!(y.is_even());
+        assert_eq!(p1, p2);
+        assert_ne!(p1, p3);
+    }
+
+    fn from_hex(s: &str) -> El {
+        El::from_bytes(&hex::decode(s).unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn it_expands_messages_with_xmd() {
+        // RFC 9380 K.1 expand_message_xmd(SHA-256)
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let long_dst = b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-1111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111";
+
+        assert_eq!(
+            expand_message_xmd(b"", dst, 0x20),
+            hex::decode("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")
+                .unwrap()
+        );
+        assert_eq!(
+            expand_message_xmd(b"abc", dst, 0x20),
+            hex::decode("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615")
+                .unwrap()
+        );
+        assert_eq!(
+            expand_message_xmd(b"", dst, 0x80),
+            hex::decode(
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+             e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+             eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+             c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            expand_message_xmd(b"", long_dst, 0x20),
+            hex::decode("e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3")
+                .unwrap()
+        );
+        assert_eq!(expand_message_xmd(b"abc", dst, 0x21).len(), 0x21);
+    }
+
+    #[test]
+    fn it_maps_to_curve_with_sswu() {
+        use crate::ecc::B;
+
+        // RFC 9380 J.8.1 secp256k1_XMD:SHA-256_SSWU_RO_, (u0, Q0) and (u1, Q1) for msg = ""
+        let vectors = [
+            (
+                "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+                "74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e",
+                "c174fa322ab7c192e11748beed45b508e9fdb1ce046dee9c2cd3a2a86b410936",
+            ),
+            (
+                "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16",
+                "44548adb1b399263ded3510554d28b4bead34b8cf9a37b4bd0bd2ba4db87ae63",
+                "96eb8e2faf05e368efe5957c6167001760233e6dd2487516b46ae725c4cce0c6",
+            ),
+        ];
+
+        for (u, x, y) in vectors.iter() {
+            let p = simplified_swu_map(&from_hex(u));
+            assert_eq!(p.y.square(), iso_curve_rhs(&p.x));
+
+            let q = iso_map(&p);
+            assert_eq!(q, Pt::new(from_hex(x), from_hex(y)));
+            assert_eq!(q.y.square(), q.x.square().mul_add(&q.x, &B));
+        }
+
+        // hash_to_field gives u0 and u1, and P = Q0 + Q1
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+        let [u0, u1] = hash_to_field(b"", dst);
+        assert_eq!(u0, from_hex(vectors[0].0));
+        assert_eq!(u1, from_hex(vectors[1].0));
+        let q0 = Pt::new(from_hex(vectors[0].1), from_hex(vectors[0].2));
+        let q1 = Pt::new(from_hex(vectors[1].1), from_hex(vectors[1].2));
+        let p = Pt::new(
+            from_hex("c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346"),
+            from_hex("64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067"),
+        );
+        assert_eq!(q0 + q1, p);
+        assert_eq!(hash_to_curve(b"", dst), p);
+
+        // tv1 = 0 for u = 0
+        assert!(iso_map(&simplified_swu_map(&El::from_u64(0))).is_on_curve());
+        assert!(iso_map(&INFINITY).inf);
+    }
+}
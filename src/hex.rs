@@ -0,0 +1,45 @@
+use std::fmt;
+
+use crate::error::Error;
+
+/// Decode a hexadecimal string, both lower and upper case are accepted
+pub fn decode(s: &str) -> Result<Vec<u8>, Error> {
+    fn nibble(c: u8) -> Result<u8, Error> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(Error::InvalidHex),
+        }
+    }
+    let s = s.as_bytes();
+
+    if s.len() & 1 != 0 {
+        return Err(Error::InvalidHex);
+    }
+
+    s.chunks(2)
+        .map(|c| Ok(nibble(c[0])? << 4 | nibble(c[1])?))
+        .collect()
+}
+
+/// Write a buffer as lowercase hexadecimal
+pub fn write(f: &mut fmt::Formatter, bin: &[u8]) -> fmt::Result {
+    for b in bin {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_decodes_hex() {
+        assert_eq!(decode("00ff7fAb").unwrap(), vec![0x00, 0xff, 0x7f, 0xab]);
+        assert_eq!(decode("").unwrap(), Vec::<u8>::new());
+        assert_eq!(decode("abc").unwrap_err(), Error::InvalidHex);
+        assert_eq!(decode("0g").unwrap_err(), Error::InvalidHex);
+    }
+}
@@ -1,26 +1,34 @@
+#[cfg(feature = "secp256k1")]
 use hmac::{Hmac, Mac, NewMac};
 use sha2::{Digest, Sha256};
-use std::convert::TryInto;
 
+#[cfg(feature = "secp256k1")]
 type HmacSha256 = Hmac<Sha256>;
 
+/// sha256 digest
+pub fn sha256(msg: &[u8]) -> [u8; 32] {
+    Sha256::digest(msg).into()
+}
+
 /// sha256 digest x2
 pub fn hash256(msg: &[u8]) -> [u8; 32] {
     let d1 = Sha256::digest(msg);
 
-    Sha256::digest(&d1)
-        .as_slice()
-        .try_into()
-        .expect("digest should be 32 bytes")
+    Sha256::digest(&d1).into()
+}
+
+/// sha256 digest prefixed by the sha256 of a tag, twice (BIP-340)
+pub fn tagged_hash(tag: &[u8], msg: &[u8]) -> [u8; 32] {
+    let t = Sha256::digest(tag);
+
+    Sha256::new().chain(t).chain(t).chain(msg).finalize().into()
 }
 
 /// hash buffer with secret key k
+#[cfg(feature = "secp256k1")]
 pub fn hmac256(k: &[u8; 32], buf: &[u8]) -> [u8; 32] {
     let mut hm = HmacSha256::new_from_slice(k).unwrap();
     hm.update(buf);
 
-    hm.finalize()
-        .into_bytes()
-        .try_into()
-        .expect("hmac should be 32 bytes")
+    hm.finalize().into_bytes().into()
 }
@@ -0,0 +1,487 @@
+use std::fmt;
+use std::ops::{Deref, Mul};
+
+use subtle::{Choice, ConditionallySelectable};
+
+use crate::ecc::Pt;
+use crate::field::El;
+use crate::scalar::Scalar;
+
+/// Represent a point in jacobian coordinates, x = X / Z^2 and y = Y / Z^3
+///
+/// Additions and doublings don't need any field inversion, only the
+/// conversion back to affine coordinates does.
+/// Coordinates are always kept reduced.
+#[derive(Clone, Copy, Debug, Eq)]
+pub struct JacobianPt {
+    pub x: El,
+    pub y: El,
+    pub z: El,
+    pub inf: bool,
+}
+
+/// 3 * b, with b = 7 the constant of the curve equation
+const B3: u64 = 21;
+
+pub const JACOBIAN_INFINITY: JacobianPt = JacobianPt {
+    x: El::new(0, 0, 0, 0),
+    y: El::new(0, 0, 0, 0),
+    z: El::new(0, 0, 0, 0),
+    inf: true,
+};
+
+impl JacobianPt {
+    /// Convert an affine point, Z = 1
+    pub fn from_affine(p: &Pt) -> Self {
+        if p.inf {
+            return JACOBIAN_INFINITY;
+        }
+
+        Self {
+            x: p.x.reduced(),
+            y: p.y.reduced(),
+            z: El::from_u64(1),
+            inf: false,
+        }
+    }
+
+    /// Convert to affine coordinates, None for the point at infinity
+    ///
+    /// This requires an inversion, unless Z = 1 e.g. for a point built with
+    /// `from_affine`
+    pub fn to_affine(&self) -> Option<Pt> {
+        if self.inf {
+            return None;
+        }
+        if self.z == El::ONE {
+            return Some(Pt::new(self.x, self.y));
+        }
+
+        let mut zinv = self.z;
+        zinv.inverse();
+        let zinv2 = zinv.square();
+
+        Some(Pt::new(
+            (self.x * zinv2).reduced(),
+            (self.y * zinv2 * zinv).reduced(),
+        ))
+    }
+
+    /// Convert to affine coordinates with a constant-time inversion
+    ///
+    /// Same as `to_affine` but Z is inverted with `El::inverse_ct`, for points
+    /// that depend on a secret e.g. the result of `mul_ladder`
+    pub fn to_affine_ct(&self) -> Option<Pt> {
+        if self.inf {
+            return None;
+        }
+
+        let mut zinv = self.z;
+        zinv.inverse_ct();
+        let zinv2 = zinv.square();
+
+        Some(Pt::new(
+            (self.x * zinv2).reduced(),
+            (self.y * zinv2 * zinv).reduced(),
+        ))
+    }
+
+    /// Calculate 2 * self
+    pub fn double(&self) -> Self {
+        if self.inf {
+            return *self;
+        }
+
+        self.double_zz(&self.z.square())
+    }
+
+    /// Calculate self + rhs
+    pub fn add(&self, rhs: &Self) -> Self {
+        if self.inf {
+            return *rhs;
+        }
+
+        self.add_zz(&self.z.square(), rhs)
+    }
+
+    /// Calculate self + rhs without any branch, the formulas are complete
+    ///
+    /// This is algorithm 7 of Renes, Costello and Batina, "Complete addition
+    /// formulas for prime order elliptic curves" (2015), for a = 0. It works
+    /// in homogeneous projective coordinates, x = X / Z and y = Y / Z, where
+    /// the point at infinity is (0 : 1 : 0), so both points are converted
+    /// with masks and the result is converted back. The same formulas handle
+    /// self == rhs, self == -rhs and the point at infinity.
+    pub fn add_ct(&self, rhs: &Self) -> Self {
+        let (x1, y1, z1) = self.to_projective();
+        let (x2, y2, z2) = rhs.to_projective();
+
+        let t0 = x1 * x2;
+        let t1 = y1 * y2;
+        let t2 = z1 * z2;
+        // t3 = (x1 + y1) * (x2 + y2) - t0 - t1 = x1 * y2 + x2 * y1
+        let t3 = ((x1 + y1) * (x2 + y2) - (t0 + t1).reduced()).reduced();
+        // t4 = (y1 + z1) * (y2 + z2) - t1 - t2 = y1 * z2 + y2 * z1
+        let t4 = ((y1 + z1) * (y2 + z2) - (t1 + t2).reduced()).reduced();
+        // y3 = (x1 + z1) * (x2 + z2) - t0 - t2 = x1 * z2 + x2 * z1
+        let y3 = ((x1 + z1) * (x2 + z2) - (t0 + t2).reduced()).reduced();
+        let t0 = (t0 * 3u64).reduced();
+        let t2 = (t2 * B3).reduced();
+        let z3 = t1 + t2;
+        let t1 = (t1 - t2).reduced();
+        let y3 = (y3 * B3).reduced();
+        // x3 = t3 * t1 - t4 * y3
+        let x3 = (t3 * t1 - t4 * y3).reduced();
+        // y3 = t1 * z3 + y3 * t0
+        let y3 = (t1 * z3 + y3 * t0).reduced();
+        // z3 = z3 * t4 + t0 * t3
+        let z3 = (z3 * t4 + t0 * t3).reduced();
+
+        // back to jacobian coordinates, (X * Z, Y * Z^2, Z) is (0, 0, 0) for O
+        let zz = z3.square();
+        Self {
+            x: (x3 * z3).reduced(),
+            y: (y3 * zz).reduced(),
+            z: z3,
+            inf: z3.is_zero(),
+        }
+    }
+
+    /// Convert to homogeneous projective coordinates (X * Z, Y, Z^3), with a
+    /// mask for the point at infinity (0 : 1 : 0)
+    fn to_projective(&self) -> (El, El, El) {
+        let mut x = (self.x * self.z).reduced();
+        let mut y = self.y;
+        let mut z = (self.z.square() * self.z).reduced();
+        let inf = Choice::from(self.inf as u8);
+
+        x.conditional_assign(&El::ZERO, inf);
+        y.conditional_assign(&El::ONE, inf);
+        z.conditional_assign(&El::ZERO, inf);
+
+        (x, y.reduced(), z)
+    }
+
+    /// Calculate both 2 * self and self + q, as needed by a Montgomery ladder step
+    ///
+    /// Z^2 of self is computed once and shared by the doubling and the addition
+    pub fn double_and_add_step(&self, q: &Self) -> (Self, Self) {
+        if self.inf {
+            return (*self, *q);
+        }
+
+        let zz = self.z.square();
+        (self.double_zz(&zz), self.add_zz(&zz, q))
+    }
+
+    /// Calculate self + rhs with the mixed formulas, rhs is affine with Z = 1
+    pub fn add_affine(&self, rhs: &Pt) -> Self {
+        if self.inf {
+            return Self::from_affine(rhs);
+        }
+        self.add_affine_zz(&self.z.square(), rhs)
+    }
+
+    /// Calculate 2 * self, plus addend if cond is set
+    ///
+    /// This is the step of the left-to-right binary method. The affine addend
+    /// has Z = 1, so the addition uses the cheaper mixed formulas. It branches
+    /// on cond and must not be used with secret scalars
+    pub fn dbl_add(&self, addend: &Pt, cond: bool) -> Self {
+        let r = self.double();
+
+        if !cond {
+            return r;
+        }
+        r.add_affine(addend)
+    }
+
+    /// Multiply the point with a scalar using a Montgomery ladder
+    ///
+    /// Every one of the 256 bits of k costs a ladder step, and the two points
+    /// are swapped with masks rather than branches on the bits. The doubling
+    /// and the addition of a step both use the complete formulas of
+    /// `JacobianPt::add_ct`, so nothing branches on the point at infinity.
+    pub fn mul_ladder(&self, k: &Scalar) -> Self {
+        let mut r0 = SilentPoint(JACOBIAN_INFINITY);
+        let mut r1 = SilentPoint(*self);
+        let mut swap = false;
+
+        // r1 - r0 = self
+        for bit in k.bits() {
+            r0.0.cswap(&mut r1.0, swap ^ bit);
+            swap = bit;
+
+            let (dbl, add) = (r0.add_ct(&r0), r0.add_ct(&r1));
+            r0 = SilentPoint(dbl);
+            r1 = SilentPoint(add);
+        }
+        r0.0.cswap(&mut r1.0, swap);
+
+        r0.0
+    }
+
+    fn cswap(&mut self, rhs: &mut Self, flag: bool) {
+        El::cswap(&mut self.x, &mut rhs.x, flag);
+        El::cswap(&mut self.y, &mut rhs.y, flag);
+        El::cswap(&mut self.z, &mut rhs.z, flag);
+
+        let t = (self.inf ^ rhs.inf) & flag;
+        self.inf ^= t;
+        rhs.inf ^= t;
+    }
+
+    /// Doubling with Z^2 already computed (dbl-2007-bl, a = 0)
+    fn double_zz(&self, zz: &El) -> Self {
+        if self.y.is_zero() {
+            return JACOBIAN_INFINITY;
+        }
+
+        let xx = self.x.square();
+        let yy = self.y.square();
+        let yyyy = yy.square();
+
+        // s = 2 * ((x + yy)^2 - xx - yyyy)
+        let s = (((self.x + yy).square() - xx - yyyy) * 2u64).reduced();
+        // m = 3 * xx
+        let m = xx * 3u64;
+        // x3 = m^2 - 2 * s
+        let x3 = (m.square() - (s * 2u64).reduced()).reduced();
+        // y3 = m * (s - x3) - 8 * yyyy
+        let y3 = (m * (s - x3) - (yyyy * 8u64).reduced()).reduced();
+        // z3 = (y + z)^2 - yy - zz
+        let z3 = ((self.y + self.z).square() - yy - *zz).reduced();
+
+        Self { x: x3, y: y3, z: z3, inf: false }
+    }
+
+    /// Addition with Z^2 of self already computed (add-2007-bl)
+    fn add_zz(&self, z1z1: &El, rhs: &Self) -> Self {
+        if rhs.inf {
+            return *self;
+        }
+
+        let z2z2 = rhs.z.square();
+        let u1 = self.x * z2z2;
+        let u2 = rhs.x * *z1z1;
+        let s1 = self.y * rhs.z * z2z2;
+        let s2 = rhs.y * self.z * *z1z1;
+        let h = (u2 - u1).reduced();
+        let r = (s2 - s1).reduced();
+
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.double_zz(z1z1);
+            }
+            return JACOBIAN_INFINITY;
+        }
+
+        // i = (2 * h)^2, j = h * i, v = u1 * i
+        let i = (h * 2u64).square();
+        let j = h * i;
+        let r = r * 2u64;
+        let v = u1 * i;
+        // x3 = r^2 - j - 2 * v
+        let x3 = (r.square() - j - (v * 2u64).reduced()).reduced();
+        // y3 = r * (v - x3) - 2 * s1 * j
+        let y3 = (r * (v - x3) - (s1 * j * 2u64).reduced()).reduced();
+        // z3 = ((z1 + z2)^2 - z1z1 - z2z2) * h
+        let z3 = (((self.z + rhs.z).square() - *z1z1 - z2z2) * h).reduced();
+
+        Self { x: x3, y: y3, z: z3, inf: false }
+    }
+
+    /// Mixed addition with an affine point, Z^2 of self already computed (madd-2007-bl)
+    fn add_affine_zz(&self, z1z1: &El, rhs: &Pt) -> Self {
+        if rhs.inf {
+            return *self;
+        }
+
+        let u2 = rhs.x * *z1z1;
+        let s2 = rhs.y * self.z * *z1z1;
+        let h = (u2 - self.x).reduced();
+        let r = (s2 - self.y).reduced();
+
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.double_zz(z1z1);
+            }
+            return JACOBIAN_INFINITY;
+        }
+
+        // hh = h^2, i = 4 * hh, j = h * i, v = x1 * i
+        let hh = h.square();
+        let i = hh * 4u64;
+        let j = h * i;
+        let r = r * 2u64;
+        let v = self.x * i;
+        // x3 = r^2 - j - 2 * v
+        let x3 = (r.square() - j - (v * 2u64).reduced()).reduced();
+        // y3 = r * (v - x3) - 2 * y1 * j
+        let y3 = (r * (v - x3) - (self.y * j * 2u64).reduced()).reduced();
+        // z3 = (z1 + h)^2 - z1z1 - hh
+        let z3 = ((self.z + h).square() - *z1z1 - hh).reduced();
+
+        Self { x: x3, y: y3, z: z3, inf: false }
+    }
+}
+
+/// Wrap intermediate points depending on secrets so they are never printed
+#[derive(Clone, Copy)]
+pub struct SilentPoint(pub JacobianPt);
+
+impl fmt::Debug for SilentPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+
+impl Deref for SilentPoint {
+    type Target = JacobianPt;
+
+    fn deref(&self) -> &JacobianPt {
+        &self.0
+    }
+}
+
+impl Mul<&Scalar> for JacobianPt {
+    type Output = JacobianPt;
+
+    /// Constant-time multiplication, see `JacobianPt::mul_ladder`
+    fn mul(self, rhs: &Scalar) -> JacobianPt {
+        self.mul_ladder(rhs)
+    }
+}
+
+impl Mul<Scalar> for JacobianPt {
+    type Output = JacobianPt;
+
+    fn mul(self, rhs: Scalar) -> JacobianPt {
+        self.mul_ladder(&rhs)
+    }
+}
+
+impl PartialEq for JacobianPt {
+    /// Points are equal if X1 * Z2^2 == X2 * Z1^2 and Y1 * Z2^3 == Y2 * Z1^3
+    fn eq(&self, rhs: &Self) -> bool {
+        if self.inf || rhs.inf {
+            return self.inf == rhs.inf;
+        }
+
+        let z1z1 = self.z.square();
+        let z2z2 = rhs.z.square();
+
+        self.x * z2z2 == rhs.x * z1z1 && self.y * z2z2 * rhs.z == rhs.y * z1z1 * self.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecc::{G, INFINITY};
+    use crate::scalar::Scalar;
+
+    #[test]
+    fn it_doubles_and_adds_jacobian_points() {
+        let g = JacobianPt::from_affine(&G);
+        let g2 = g.double();
+        let g3 = g2.add(&g);
+
+        assert_eq!(g.to_affine(), Some(G));
+        assert_eq!(JACOBIAN_INFINITY.to_affine(), None);
+        assert_eq!(g2.to_affine().unwrap(), G * Scalar::from_u64(2));
+        assert_eq!(g3.to_affine().unwrap(), G * Scalar::from_u64(3));
+        assert_eq!(g.add(&g), g2);
+        assert_eq!(g2.add(&g2), g2.double());
+        assert_eq!(g.add(&JACOBIAN_INFINITY), g);
+        assert_eq!(JACOBIAN_INFINITY.add(&g), g);
+
+        let mut minus_g = G;
+        minus_g.y = minus_g.y.negate(1).reduced();
+        assert!(g.add(&JacobianPt::from_affine(&minus_g)).inf);
+    }
+
+    #[test]
+    fn it_multiplies_with_a_ladder() {
+        let g = JacobianPt::from_affine(&G);
+        let k = Scalar::from_bytes(&[0xa5; 32]);
+
+        assert_eq!((g * k).to_affine().unwrap(), G.mul_vartime(&k));
+        assert_eq!((g * k).to_affine_ct(), (g * k).to_affine());
+        assert_eq!(g.to_affine_ct(), Some(G));
+        assert_eq!(JACOBIAN_INFINITY.to_affine_ct(), None);
+        assert_eq!(g * Scalar::from_u64(3), g.double().add(&g));
+        assert_eq!(g * Scalar::from_u64(1), g);
+        assert!((g * Scalar::from_u64(0)).inf);
+        assert!((JACOBIAN_INFINITY * k).inf);
+    }
+
+    #[test]
+    fn it_doubles_and_adds_an_affine_point() {
+        let g = JacobianPt::from_affine(&G);
+        let p = JacobianPt::from_affine(&(G * Scalar::from_u64(5))).double();
+
+        assert_eq!(p.dbl_add(&G, false), p.double());
+        assert_eq!(p.dbl_add(&G, true), p.double().add(&g));
+        assert_eq!(
+            p.dbl_add(&G, true).to_affine().unwrap(),
+            G * Scalar::from_u64(21)
+        );
+        assert_eq!(p.dbl_add(&INFINITY, true), p.double());
+        assert_eq!(JACOBIAN_INFINITY.dbl_add(&G, true), g);
+        assert!(JACOBIAN_INFINITY.dbl_add(&G, false).inf);
+
+        // G + G takes the doubling case of the mixed addition, 2 * G - 2 * G is O
+        let mut minus_g2 = G * Scalar::from_u64(2);
+        minus_g2.y = minus_g2.y.negate(1).reduced();
+        assert_eq!(g.add_affine_zz(&El::ONE, &G), g.double());
+        assert!(g.dbl_add(&minus_g2, true).inf);
+    }
+
+    #[test]
+    fn it_adds_with_complete_formulas() {
+        let g = JacobianPt::from_affine(&G);
+        let p = JacobianPt::from_affine(&(G * Scalar::from_u64(5))).double();
+        let mut minus_p = p;
+        minus_p.y = minus_p.y.negate(1).reduced();
+
+        assert_eq!(p.add_ct(&g), p.add(&g));
+        assert_eq!(p.add_ct(&g).to_affine().unwrap(), G * Scalar::from_u64(11));
+        assert_eq!(p.add_ct(&p), p.double());
+        assert_eq!(g.add_ct(&JACOBIAN_INFINITY), g);
+        assert_eq!(JACOBIAN_INFINITY.add_ct(&p), p);
+        assert_eq!(
+            JACOBIAN_INFINITY.add_ct(&JACOBIAN_INFINITY),
+            JACOBIAN_INFINITY
+        );
+
+        let o = p.add_ct(&minus_p);
+        assert!(o.inf);
+        assert_eq!(o.x, El::ZERO);
+        assert_eq!(o.z, El::ZERO);
+    }
+
+    #[test]
+    fn it_hides_silent_points() {
+        let p = SilentPoint(JacobianPt::from_affine(&G));
+
+        assert_eq!(format!("{:?}", p), "<redacted>");
+        assert_eq!(p.to_affine().unwrap(), G);
+    }
+
+    #[test]
+    fn it_runs_a_ladder_step() {
+        let p = JacobianPt::from_affine(&(G * Scalar::from_u64(5)));
+        let q = JacobianPt::from_affine(&(G * Scalar::from_u64(6))).double();
+        let (dbl, add) = p.double_and_add_step(&q);
+
+        assert_eq!(dbl, p.double());
+        assert_eq!(add, p.add(&q));
+        assert_eq!(dbl.to_affine().unwrap(), G * Scalar::from_u64(10));
+        assert_eq!(add.to_affine().unwrap(), G * Scalar::from_u64(17));
+
+        let (dbl, add) = JACOBIAN_INFINITY.double_and_add_step(&q);
+        assert!(dbl.inf);
+        assert_eq!(add, q);
+    }
+}
@@ -1,13 +1,24 @@
 use std::convert::TryInto;
+use std::str::FromStr;
 
-use crate::ecc::{Pt, G, SECP256K1_B};
+use crate::ecc::{is_in_group, Pt, B, G};
 use crate::error::Error;
 use crate::field::El;
-use crate::hmac::{hash256, hmac256};
+use crate::hex;
+use crate::hmac::hash256;
+use crate::jacobian::{JacobianPt, JACOBIAN_INFINITY};
+use crate::nonce::{NonceFunction, RFC6979NonceFunction};
+use crate::rng::{random_scalar, SecureRandom};
 use crate::scalar::Scalar;
+use crate::schnorr::schnorr_verify;
 use crate::sig::Signature;
+use crate::table::{mul2_vartime, PointTable};
+use crate::util::ct_eq_bytes;
 
 /// Represent a private key including a secret
+///
+/// It is `Clone` but not `Copy`, so copies of the secret are always explicit
+#[derive(Clone)]
 pub struct PrivateKey {
     secret: Scalar,
 }
@@ -31,49 +42,20 @@ impl PrivateKey {
         Self { secret }
     }
 
-    fn calculate_k(&self, z: &Scalar) -> Scalar {
-        let zbytes = z.to_bytes();
-        let secbytes = self.secret.to_bytes();
-        let mut v = [0x01; 32];
-        let mut k = [0x00; 32];
-        let mut tmp = [0x01; 97]; // v[32] + secret[32] + z[32] + 1
-        let n_1 = Scalar::from_u64(1);
-
-        // tmp[0..32] = 1
-        tmp[32] = 0u8;
-        tmp[33..65].copy_from_slice(&secbytes[..]);
-        tmp[65..97].copy_from_slice(&zbytes[..]);
-
-        // K = HMAC_K(V || 0x00 || secret || z)
-        k = hmac256(&k, &tmp);
-        // V = HMAC_K(V)
-        v = hmac256(&k, &v);
-
-        tmp[0..32].copy_from_slice(&v);
-        tmp[32] = 1u8;
-
-        // K = HMAC_K(V || 0x01 || secret || z)
-        k = hmac256(&k, &tmp);
-        // V = HMAC_K(V)
-        v = hmac256(&k, &v);
-
-        tmp[32] = 0u8;
-
-        loop {
-            // V = HMAC_K(V)
-            v = hmac256(&k, &v);
+    /// Generate a private key with a secret drawn uniformly in [1, N)
+    ///
+    /// Any generator implementing `SecureRandom` works, including every
+    /// `rand_core` generator marked as `CryptoRng`
+    pub fn random(rng: &mut impl SecureRandom) -> Self {
+        Self::new(random_scalar(rng))
+    }
 
-            let res = Scalar::from_bytes(&v);
-            if res >= n_1 && res.get_overflow() == 0 {
-                return res;
-            }
+    pub(crate) fn secret(&self) -> &Scalar {
+        &self.secret
+    }
 
-            // K = HMAC_K(V || 0x00)
-            tmp[0..32].copy_from_slice(&v);
-            k = hmac256(&k, &tmp[0..32]);
-            // V = HMAC_K(V)
-            v = hmac256(&k, &v);
-        }
+    pub(crate) fn calculate_k(&self, z: &Scalar) -> Scalar {
+        RFC6979NonceFunction.generate(&self.secret.to_bytes(), &z.to_bytes(), &[], &[0u8; 32])
     }
 
     /// Create a signature from a hash
@@ -94,16 +76,66 @@ impl PrivateKey {
     /// let sig = pk.sign(&hash);
     /// ```
     pub fn sign(&self, z: &Scalar) -> Signature {
-        let mut k = self.calculate_k(z);
-        let r = G * &k;
-        let rx = r.x.to_scalar();
+        let mut data = [0u8; 32];
+
+        // r = 0 or s = 0 is very unlikely, retry with extra data if it happens
+        loop {
+            if let Ok(sig) = self.sign_with_nonce(z, &RFC6979NonceFunction, &data) {
+                return sig;
+            }
+            data[31] = data[31].wrapping_add(1);
+        }
+    }
+
+    /// Create a signature from a hash with a custom nonce function
+    ///
+    /// data is given to the nonce function, e.g. as extra entropy for
+    /// [`RFC6979NonceFunction`] which is what [`sign`](Self::sign) uses.
+    ///
+    /// Fails with `OutOfRange` if the nonce is not in [1, N - 1], and with
+    /// `InvalidSignature` if r or s is 0, in which case another nonce must be
+    /// used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use estel_secp256k1::*;
+    ///
+    /// let secret = Scalar::from_bytes(&hash256("the force".as_bytes()));
+    /// let pk = PrivateKey::new(secret);
+    /// let hash = Scalar::from_bytes(&hash256("The greatest teacher failure is".as_bytes()));
+    /// let sig = pk.sign_with_nonce(&hash, &RFC6979NonceFunction, &[0x42; 32]).unwrap();
+    /// ```
+    pub fn sign_with_nonce(
+        &self,
+        z: &Scalar,
+        nonce: &impl NonceFunction,
+        data: &[u8; 32],
+    ) -> Result<Signature, Error> {
+        let mut k = nonce.generate(&self.secret.to_bytes(), &z.to_bytes(), &[], data);
+        // the nonce function is not trusted to return a valid scalar
+        if k.is_zero() || k.get_overflow() != 0 || k.is_negative() {
+            return Err(Error::OutOfRange);
+        }
+        let r = G * k;
+        // r.x < P but it can be greater than N
+        let mut rx = r.x.to_scalar();
+        rx.reduce(rx.get_overflow());
 
         k.modinv_inner();
 
+        // z + rx * secret must be reduced before the multiplication
+        let mut zn = *z;
+        zn.reduce(zn.get_overflow());
+        let t = rx.mul_add(&self.secret, &zn);
+
         // s = ((z + rx * secret) / k) % N
-        let s = (*z + rx.mulmod(&self.secret)).mulmod(&k);
+        let s = t.mulmod(&k);
 
-        Signature { r: rx, s }
+        if rx.is_zero() || s.is_zero() {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(Signature { r: rx, s })
     }
 
     /// Create a signature from a buffer
@@ -130,10 +162,19 @@ impl PrivateKey {
     }
 }
 
+/// Secrets are compared in constant time
+impl PartialEq for PrivateKey {
+    fn eq(&self, rhs: &Self) -> bool {
+        ct_eq_bytes(&self.secret.to_bytes(), &rhs.secret.to_bytes())
+    }
+}
+
+impl Eq for PrivateKey {}
+
 /// Represent a public key containing an ECC point
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct PublicKey {
-    key: Pt,
+    pub(crate) key: Pt,
 }
 
 impl PublicKey {
@@ -147,21 +188,80 @@ impl PublicKey {
         Self { key: Pt::new(x, y) }
     }
 
+    /// Add 2 public keys
+    ///
+    /// Fails if the sum is the point at infinity, e.g. when b = -a
+    pub fn combine(a: &PublicKey, b: &PublicKey) -> Result<PublicKey, Error> {
+        Self::combine_many(&[*a, *b])
+    }
+
+    /// Add any number of public keys
+    ///
+    /// The sum is accumulated in jacobian coordinates, so there is a single
+    /// inversion. Fails if there are no keys or if the sum is the point at infinity
+    pub fn combine_many(keys: &[PublicKey]) -> Result<PublicKey, Error> {
+        let sum = keys.iter().fold(JACOBIAN_INFINITY, |acc, pk| {
+            acc.add(&JacobianPt::from_affine(&pk.key))
+        });
+
+        let key = sum.to_affine().ok_or(Error::InvalidPoint)?;
+
+        Ok(Self { key })
+    }
+
     /// Verify that a signature is valid for a given hash
     ///
     /// This validates that a signature was generated from the same secret used
     /// for the public key.
     /// It's also possible to use [`verify`] if you calculate the hash.
+    #[must_use]
     pub fn verify(&self, z: &Scalar, sig: &Signature) -> bool {
+        self.verify_with_table(z, sig, &self.point_table())
+    }
+
+    /// Precompute the table of the key for `PublicKey::verify_with_table`
+    pub fn point_table(&self) -> PointTable {
+        PointTable::precompute(&self.key, 4)
+    }
+
+    /// Verify that a signature is valid for a given hash, with a table of the key
+    ///
+    /// Same as [`PublicKey::verify`] but the odd multiples of the key are
+    /// computed once by the caller with [`PublicKey::point_table`] and kept to
+    /// verify many signatures of the same key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base point of the table is not the public key
+    #[must_use]
+    pub fn verify_with_table(&self, z: &Scalar, sig: &Signature, table: &PointTable) -> bool {
+        assert!(
+            *table.base() == JacobianPt::from_affine(&self.key),
+            "the table is not the one of the public key"
+        );
+        // r and s must be in [1, N - 1]
+        if sig.r.is_zero() || sig.r.get_overflow() != 0 {
+            return false;
+        }
+        if sig.s.is_zero() || sig.s.get_overflow() != 0 {
+            return false;
+        }
+
         let mut s_inv = sig.s;
 
         s_inv.modinv_inner();
 
         let u = z.mulmod(&s_inv);
         let v = sig.r.mulmod(&s_inv);
-        let r = G * &u + self.key * &v;
+        let r = match mul2_vartime(&u, &v, table).to_affine() {
+            Some(r) => r,
+            None => return false,
+        };
+
+        let mut rx = r.x.to_scalar();
+        rx.reduce(rx.get_overflow());
 
-        sig.r == r.x.to_scalar()
+        sig.r == rx
     }
 
     /// Verify that a signature is valid for a given buffer
@@ -169,6 +269,7 @@ impl PublicKey {
     /// This validates that a signature was generated from the same secret used
     /// for the public key.
     /// It's also possible to use [`verify`] if you calculate the hash.
+    #[must_use]
     pub fn verify_buffer(&self, buf: &[u8], sig: &Signature) -> bool {
         let hash = hash256(buf);
         let z = Scalar::from_bytes(&hash);
@@ -210,7 +311,27 @@ impl PublicKey {
         res
     }
 
+    /// Parse a SEC1 encoded public key (compressed or uncompressed)
+    ///
+    /// Fails if the buffer is malformed or if the point is not in the group
+    /// (see [`is_in_group`](crate::is_in_group))
     pub fn parse_sec(bin: &[u8]) -> Result<Self, Error> {
+        let mut pk = Self::parse_sec_unchecked(bin)?;
+
+        pk.key.x.reduce();
+        pk.key.y.reduce();
+        if !is_in_group(&pk.key) {
+            return Err(Error::InvalidPoint);
+        }
+
+        Ok(pk)
+    }
+
+    fn parse_sec_unchecked(bin: &[u8]) -> Result<Self, Error> {
+        match (bin.len(), bin.first()) {
+            (33, Some(0x02)) | (33, Some(0x03)) | (65, Some(0x04)) => {}
+            _ => return Err(Error::InvalidBuffer),
+        }
         let xbin: [u8; 32] = bin[1..33].try_into().or(Err(Error::InvalidBuffer))?;
 
         if bin[0] == 0x04 {
@@ -229,8 +350,7 @@ impl PublicKey {
             let is_even = bin[0] == 0x02;
 
             let x = El::from_bytes(&xbin);
-            let x3 = x.square() * x;
-            let y2 = x3 + El::from_u64(SECP256K1_B);
+            let y2 = x.square().mul_add(&x, &B);
             let (_y, is_valid) = y2.sqrt();
 
             if !is_valid {
@@ -254,9 +374,214 @@ impl PublicKey {
     }
 }
 
+/// Parse a public key from its hexadecimal SEC1 encoding
+///
+/// Both compressed (66 characters) and uncompressed (130 characters) keys are
+/// accepted
+impl FromStr for PublicKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        PublicKey::parse_sec(&hex::decode(s)?)
+    }
+}
+
+/// Represent a BIP-340 public key
+///
+/// Only the x coordinate is serialized, the point is implicitly the one with
+/// an even y coordinate
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct XOnlyPublicKey {
+    pub(crate) key: Pt,
+}
+
+impl XOnlyPublicKey {
+    /// Create an x-only public key from a public key, dropping the y parity
+    pub fn from_public_key(pk: &PublicKey) -> Self {
+        let mut key = pk.key;
+
+        key.x.reduce();
+        key.y.reduce();
+        if !key.y.is_even() {
+            key.y = key.y.negate(1);
+            key.y.reduce();
+        }
+
+        Self { key }
+    }
+
+    /// Parse a 32 bytes x coordinate, this is lift_x in BIP-340
+    pub fn parse(bin: &[u8; 32]) -> Result<Self, Error> {
+        let mut sec = [0x02u8; 33];
+
+        sec[1..33].copy_from_slice(bin);
+
+        let pk = PublicKey::parse_sec(&sec)?;
+        // x must be lower than P
+        if pk.key.x.to_bytes() != *bin {
+            return Err(Error::InvalidPoint);
+        }
+
+        Ok(Self { key: pk.key })
+    }
+
+    /// Serialize the x coordinate in big endian
+    pub fn serialize(&self) -> [u8; 32] {
+        self.key.x.to_bytes()
+    }
+}
+
+/// Verify a signature of a message whatever the signature scheme
+pub trait VerifyKey {
+    fn verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), Error>;
+}
+
+/// Sign a message with the default signature scheme of the key
+pub trait SignKey {
+    fn sign(&self, msg: &[u8]) -> Vec<u8>;
+}
+
+impl VerifyKey for PublicKey {
+    /// Verify a signature, the scheme is chosen from its length
+    ///
+    /// 64 bytes signatures are BIP-340 signatures checked against the x-only
+    /// key, other ones are DER encoded ECDSA signatures of hash256(msg)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use estel_secp256k1::*;
+    ///
+    /// let secret = Scalar::from_bytes(&hash256("the force".as_bytes()));
+    /// let pvk = PrivateKey::new(secret);
+    /// let pk = PublicKey::from_secret(&secret);
+    /// let msg = "The greatest teacher failure is".as_bytes();
+    /// let sig = SignKey::sign(&pvk, msg);
+    ///
+    /// assert!(VerifyKey::verify(&pk, msg, &sig).is_ok());
+    /// ```
+    fn verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), Error> {
+        let valid = match sig.try_into() {
+            Ok(bin) => schnorr_verify(&XOnlyPublicKey::from_public_key(self), msg, bin),
+            Err(_) => self.verify_buffer(msg, &Signature::parse_der(sig)?),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+impl VerifyKey for XOnlyPublicKey {
+    /// Verify a 64 bytes BIP-340 signature
+    fn verify(&self, msg: &[u8], sig: &[u8]) -> Result<(), Error> {
+        let bin = sig.try_into().map_err(|_| Error::InvalidBuffer)?;
+
+        if schnorr_verify(self, msg, bin) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+impl SignKey for PrivateKey {
+    /// Create a DER encoded ECDSA signature of hash256(msg)
+    fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        let (der, len) = self.sign_buffer(msg).serialize_der();
+
+        der[..len].to_vec()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scalar::{negate_mod_n, N};
+
+    #[test]
+    fn it_compares_private_keys() {
+        let a = PrivateKey::new(Scalar::from_u64(0x2a));
+
+        assert!(a == PrivateKey::new(Scalar::from_u64(0x2a)));
+        assert!(a != PrivateKey::new(Scalar::from_u64(0x2b)));
+    }
+
+    #[test]
+    fn it_generates_random_private_keys() {
+        struct Counter(u8);
+
+        impl SecureRandom for Counter {
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.0 = self.0.wrapping_add(1);
+                dest.fill(self.0);
+            }
+        }
+
+        let mut rng = Counter(0);
+        let a = PrivateKey::random(&mut rng);
+        let b = PrivateKey::random(&mut rng);
+
+        assert!(a == PrivateKey::new(Scalar::from_bytes(&[0x01; 32])));
+        assert!(a != b);
+        // 0xff..ff is greater than N and 0 is out of range too, both are skipped
+        let mut rng = Counter(0xfe);
+        assert!(PrivateKey::random(&mut rng) == a);
+    }
+
+    /// Nonce function returning a constant, not secure
+    struct FixedNonce(Scalar);
+
+    impl NonceFunction for FixedNonce {
+        fn generate(&self, _: &[u8; 32], _: &[u8; 32], _: &[u8], _: &[u8; 32]) -> Scalar {
+            self.0
+        }
+    }
+
+    #[test]
+    fn it_signs_with_a_nonce_function() {
+        let secret = Scalar::from_bytes(&hash256(b"n00b"));
+        let pvk = PrivateKey::new(secret);
+        let pk = PublicKey::from_secret(&secret);
+        let z = Scalar::from_bytes(&hash256(b"Hello World"));
+        let k = Scalar::from_u64(0x2a);
+
+        let sig = pvk.sign_with_nonce(&z, &FixedNonce(k), &[0u8; 32]).unwrap();
+        assert_eq!(sig.r, (G * k).x.to_scalar());
+        assert!(pk.verify(&z, &sig));
+
+        assert_eq!(
+            Ok(pvk.sign(&z)),
+            pvk.sign_with_nonce(&z, &RFC6979NonceFunction, &[0u8; 32])
+        );
+        let sig = pvk
+            .sign_with_nonce(&z, &RFC6979NonceFunction, &[0x01; 32])
+            .unwrap();
+        assert_ne!(sig, pvk.sign(&z));
+        assert!(pk.verify(&z, &sig));
+    }
+
+    #[test]
+    fn it_rejects_invalid_nonces() {
+        let secret = Scalar::from_bytes(&hash256(b"n00b"));
+        let pvk = PrivateKey::new(secret);
+        let z = Scalar::from_bytes(&hash256(b"Hello World"));
+
+        for k in [Scalar::ZERO, N, N + Scalar::ONE, Scalar::ZERO - Scalar::ONE] {
+            let res = pvk.sign_with_nonce(&z, &FixedNonce(k), &[0u8; 32]);
+            assert_eq!(res, Err(Error::OutOfRange));
+        }
+
+        // z = -r * secret gives s = 0
+        let k = Scalar::from_u64(0x2a);
+        let mut r = (G * k).x.to_scalar();
+        r.reduce(r.get_overflow());
+        let z = negate_mod_n(&r.mulmod(&secret));
+        let res = pvk.sign_with_nonce(&z, &FixedNonce(k), &[0u8; 32]);
+        assert_eq!(res, Err(Error::InvalidSignature));
+    }
 
     #[test]
     fn it_tests_k_is_deterministic() {
@@ -283,6 +608,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_signs_a_hash_greater_than_n() {
+        let pvk = PrivateKey::new(Scalar::from_bytes(&hash256("n00b".as_bytes())));
+        let pk = PublicKey::from_secret(&pvk.secret);
+        let z = Scalar::from_bytes(&[0xff; 32]);
+
+        let sig = pvk.sign(&z);
+
+        assert_eq!(sig.s.get_overflow(), 0);
+        assert!(pk.verify(&z, &sig));
+    }
+
+    #[test]
+    fn it_combines_public_keys() {
+        let a = PublicKey::from_secret(&Scalar::from_u64(3));
+        let b = PublicKey::from_secret(&Scalar::from_u64(5));
+        let minus_a = PublicKey::from_secret(&(Scalar::GROUP_ORDER - Scalar::from_u64(3)));
+
+        assert_eq!(
+            PublicKey::combine(&a, &b).unwrap(),
+            PublicKey::from_secret(&Scalar::from_u64(8))
+        );
+        assert_eq!(
+            PublicKey::combine(&a, &a).unwrap(),
+            PublicKey::from_secret(&Scalar::from_u64(6))
+        );
+        assert_eq!(PublicKey::combine(&a, &minus_a), Err(Error::InvalidPoint));
+
+        assert_eq!(
+            PublicKey::combine_many(&[a, b, a, b, b]).unwrap(),
+            PublicKey::from_secret(&Scalar::from_u64(21))
+        );
+        assert_eq!(PublicKey::combine_many(&[b]).unwrap(), b);
+        assert_eq!(PublicKey::combine_many(&[a, b, minus_a]).unwrap(), b);
+        assert_eq!(PublicKey::combine_many(&[]), Err(Error::InvalidPoint));
+        assert_eq!(
+            PublicKey::combine_many(&[a, minus_a]),
+            Err(Error::InvalidPoint)
+        );
+    }
+
     #[test]
     fn it_checks_public_key_serialization() {
         let mut p = PublicKey::from_coords(
@@ -309,4 +675,100 @@ mod tests {
         assert_eq!(p, p1);
         assert_eq!(p, p2);
     }
+
+    #[test]
+    fn it_rejects_public_key_not_on_curve() {
+        let mut p = PublicKey::from_secret(&Scalar::from_u64(0x42));
+        let mut sec = p.serialize_sec_uncompressed();
+
+        sec[64] ^= 0x01;
+
+        let exp = PublicKey::parse_sec(&sec).unwrap_err();
+        assert_eq!(exp, Error::InvalidPoint);
+    }
+
+    #[test]
+    fn it_parses_hex_public_key() {
+        let g = PublicKey::from_secret(&Scalar::from_u64(1));
+        let compressed = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let uncompressed = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                            483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+        assert_eq!(compressed.parse::<PublicKey>().unwrap(), g);
+        assert_eq!(uncompressed.parse::<PublicKey>().unwrap(), g);
+    }
+
+    #[test]
+    fn it_cannot_parse_hex_public_key() {
+        let wrong_prefix = "0579be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let wrong_len = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f817";
+        let off_curve = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                         483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b9";
+
+        assert_eq!(
+            wrong_prefix.parse::<PublicKey>().unwrap_err(),
+            Error::InvalidBuffer
+        );
+        assert_eq!(
+            wrong_len.parse::<PublicKey>().unwrap_err(),
+            Error::InvalidBuffer
+        );
+        assert_eq!(
+            off_curve.parse::<PublicKey>().unwrap_err(),
+            Error::InvalidPoint
+        );
+        assert_eq!("".parse::<PublicKey>().unwrap_err(), Error::InvalidBuffer);
+        assert_eq!("02zz".parse::<PublicKey>().unwrap_err(), Error::InvalidHex);
+    }
+
+    #[test]
+    fn it_verifies_with_verify_key() {
+        let secret = Scalar::from_bytes(&hash256(b"the force"));
+        let pvk = PrivateKey::new(secret);
+        let pk = PublicKey::from_secret(&secret);
+        let msg = b"May the force be with you";
+
+        let ecdsa = SignKey::sign(&pvk, msg);
+        assert!(VerifyKey::verify(&pk, msg, &ecdsa).is_ok());
+        assert_eq!(
+            VerifyKey::verify(&pk, b"wrong", &ecdsa),
+            Err(Error::InvalidSignature)
+        );
+
+        let schnorr = crate::schnorr::schnorr_sign(&pvk, msg, &[0u8; 32]);
+        let xpk = XOnlyPublicKey::from_public_key(&pk);
+        assert!(VerifyKey::verify(&pk, msg, &schnorr).is_ok());
+        assert!(VerifyKey::verify(&xpk, msg, &schnorr).is_ok());
+        assert_eq!(
+            VerifyKey::verify(&xpk, msg, &ecdsa),
+            Err(Error::InvalidBuffer)
+        );
+    }
+
+    #[test]
+    fn it_verifies_with_a_table() {
+        let secret = Scalar::from_bytes(&hash256(b"the force"));
+        let pvk = PrivateKey::new(secret);
+        let pk = PublicKey::from_secret(&secret);
+        let table = PointTable::precompute(&pk.key, 5);
+
+        for msg in [&b"first"[..], b"second", b"third"].iter() {
+            let z = Scalar::from_bytes(&hash256(msg));
+            let sig = pvk.sign(&z);
+
+            assert!(pk.verify_with_table(&z, &sig, &table));
+            assert!(!pk.verify_with_table(&(z + Scalar::ONE), &sig, &table));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_rejects_the_table_of_another_key() {
+        let pvk = PrivateKey::new(Scalar::from_u64(0x2a));
+        let pk = PublicKey::from_secret(&Scalar::from_u64(0x2a));
+        let z = Scalar::from_bytes(&hash256(b"msg"));
+        let sig = pvk.sign(&z);
+
+        let _ = pk.verify_with_table(&z, &sig, &PointTable::precompute(&G, 4));
+    }
 }
@@ -1,16 +1,115 @@
+#![allow(clippy::identity_op, clippy::wrong_self_convention)]
+
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "bitcoin-addresses")]
+mod address;
+#[cfg(feature = "secp256k1")]
+mod batch;
+#[cfg(feature = "secp256k1")]
+mod commitment;
+#[cfg(feature = "secp256k1")]
+mod curve_info;
+#[cfg(feature = "secp256k1")]
+mod dleq;
+#[cfg(feature = "secp256k1")]
+mod drbg;
+#[cfg(feature = "secp256k1")]
 mod ecc;
 mod error;
+#[cfg(feature = "secp256k1")]
 mod field;
+#[cfg(feature = "secp256k1")]
+mod hash_to_curve;
+#[cfg(feature = "secp256k1")]
+mod hex;
 mod hmac;
+#[cfg(feature = "secp256k1")]
+mod jacobian;
+#[cfg(feature = "secp256k1")]
 mod key;
+#[cfg(feature = "secp256k1")]
+mod nonce;
+#[cfg(feature = "secp256k1")]
+mod pedersen;
+#[cfg(feature = "secp256k1")]
+mod prf;
+#[cfg(feature = "secp256k1")]
+mod rng;
 mod scalar;
+#[cfg(feature = "secp256k1")]
+mod schnorr;
+#[cfg(feature = "secp256k1")]
+mod sec1;
+#[cfg(feature = "secp256k1")]
 mod sig;
+#[cfg(feature = "secp256k1")]
+mod table;
+#[cfg(feature = "secp256k1")]
+mod taproot;
+#[cfg(feature = "secp256k1")]
+mod transcript;
+mod util;
+#[cfg(feature = "secp256k1")]
+mod vrf;
 
+#[cfg(feature = "bitcoin-addresses")]
+pub use crate::address::{p2pkh_address, p2pkh_hash, p2tr_script_pubkey, p2wpkh_script_pubkey};
+#[cfg(feature = "secp256k1")]
+pub use crate::batch::batch_verify_ecdsa;
+#[cfg(feature = "secp256k1")]
+pub use crate::commitment::Commitment;
+#[cfg(feature = "secp256k1")]
+pub use crate::curve_info::{secp256k1_info, CurveInfo};
+#[cfg(feature = "secp256k1")]
+pub use crate::dleq::{dleq_prove, dleq_verify, DleqProof};
+#[cfg(feature = "secp256k1")]
+pub use crate::drbg::HmacDrbg;
+#[cfg(feature = "secp256k1")]
+pub use crate::ecc::{cofactor_clear, is_in_group, CurveSafePoint, Pt, COFACTOR, G, INFINITY};
 pub use crate::error::Error;
-pub use crate::hmac::hash256;
-pub use crate::key::{PrivateKey, PublicKey};
-pub use crate::scalar::Scalar;
-pub use crate::sig::Signature;
+#[cfg(feature = "secp256k1")]
+pub use crate::field::{El, BETA};
+#[cfg(feature = "secp256k1")]
+pub use crate::hash_to_curve::{
+    expand_message_xmd, hash_to_curve, hash_to_curve_tai, hash_to_field, iso_map,
+    simplified_swu_map,
+};
+pub use crate::hmac::{hash256, sha256, tagged_hash};
+#[cfg(feature = "secp256k1")]
+pub use crate::jacobian::{JacobianPt, SilentPoint, JACOBIAN_INFINITY};
+#[cfg(feature = "secp256k1")]
+pub use crate::key::{PrivateKey, PublicKey, SignKey, VerifyKey, XOnlyPublicKey};
+#[doc(hidden)]
+pub use crate::macros::hex_limbs;
+#[cfg(feature = "secp256k1")]
+pub use crate::nonce::{NonceFunction, RFC6979NonceFunction};
+#[cfg(feature = "secp256k1")]
+pub use crate::pedersen::{
+    hash_to_generator, PedersenCommitment, GENERATOR_H, GENERATOR_H_DST, GENERATOR_H_MSG,
+};
+#[cfg(feature = "secp256k1")]
+pub use crate::prf::Prf;
+#[cfg(feature = "secp256k1")]
+pub use crate::rng::SecureRandom;
+pub use crate::scalar::{ModInvStrategy, PaddedScalar, Scalar, LAMBDA};
+#[cfg(feature = "secp256k1")]
+pub use crate::schnorr::{
+    schnorr_challenge, schnorr_sign, schnorr_sign_with_nonce, schnorr_verify,
+    schnorr_verify_returning_nonce, SchnorrNonce,
+};
+#[cfg(feature = "secp256k1")]
+pub use crate::sig::{BitcoinSignature, RecoverableSignature, Signature};
+#[cfg(feature = "secp256k1")]
+pub use crate::table::{mul2_vartime, PointTable};
+#[cfg(feature = "secp256k1")]
+pub use crate::taproot::verify_taproot_commitment;
+#[cfg(feature = "secp256k1")]
+pub use crate::transcript::Transcript;
+pub use crate::util::ct_eq_bytes;
+#[cfg(feature = "secp256k1")]
+pub use crate::vrf::{vrf_proof_to_hash, vrf_prove, vrf_verify, VrfProof};
 
 /// Create a keypair from a buffer seed
 ///
@@ -24,6 +123,7 @@ pub use crate::sig::Signature;
 ///
 /// let (privkey, pubkey) = generate_keypair_from_seed("some password".as_bytes());
 /// ```
+#[cfg(feature = "secp256k1")]
 pub fn generate_keypair_from_seed(seed: &[u8]) -> (PrivateKey, PublicKey) {
     let secret = Scalar::from_bytes(&hash256(seed));
     let pk = PublicKey::from_secret(&secret);
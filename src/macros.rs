@@ -0,0 +1,146 @@
+/// Parse big endian hexadecimal digits to L little endian u64 limbs
+///
+/// This is what the literal macros expand to. It indexes an empty array out
+/// of bounds, so it fails to compile when evaluated in a const, if s is not
+/// exactly 16 * L digits. panic! in a const fn needs Rust 1.57
+#[doc(hidden)]
+pub const fn hex_limbs<const L: usize>(s: &str) -> [u64; L] {
+    const WRONG_NUMBER_OF_HEX_DIGITS: [usize; 0] = [];
+    const INVALID_HEX_DIGIT: [u8; 0] = [];
+    let s = s.as_bytes();
+    let mut limbs = [0u64; L];
+
+    if s.len() != 16 * L {
+        let _ = WRONG_NUMBER_OF_HEX_DIGITS[s.len()];
+    }
+
+    let mut i = 0;
+    while i < s.len() {
+        let nibble = match s[i] {
+            b'0'..=b'9' => s[i] - b'0',
+            b'a'..=b'f' => s[i] - b'a' + 10,
+            b'A'..=b'F' => s[i] - b'A' + 10,
+            _ => INVALID_HEX_DIGIT[i],
+        };
+        // the first digit is the most significant one of the last limb
+        let limb = L - 1 - i / 16;
+        limbs[limb] = limbs[limb] << 4 | nibble as u64;
+        i += 1;
+    }
+
+    limbs
+}
+
+/// Build a `Scalar` from its 320 bits, 80 hexadecimal digits, at compile time
+///
+/// The digits are big endian, the first 16 ones are d[4] which holds the sign.
+/// A wrong number of digits fails to compile
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let p = i320!(
+///     "0000000000000000fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+/// );
+///
+/// assert_eq!(p.d[4], 0);
+/// assert_eq!(p.d[0], 0xfffffffefffffc2f);
+/// ```
+///
+/// ```compile_fail
+/// use estel_secp256k1::*;
+///
+/// let p = i320!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+/// ```
+#[macro_export]
+macro_rules! i320 {
+    ($hex:literal) => {{
+        const LIMBS: [u64; 5] = $crate::hex_limbs::<5>($hex);
+        $crate::Scalar { d: LIMBS }
+    }};
+}
+
+/// Build an `El` from 64 big endian hexadecimal digits at compile time
+///
+/// The value must be lower than P, like with `El::new`. A wrong number of
+/// digits fails to compile
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let beta = fe!("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee");
+///
+/// assert_eq!(beta, BETA);
+/// ```
+#[cfg(feature = "secp256k1")]
+#[macro_export]
+macro_rules! fe {
+    ($hex:literal) => {{
+        const LIMBS: [u64; 4] = $crate::hex_limbs::<4>($hex);
+        $crate::El::new(LIMBS[3], LIMBS[2], LIMBS[1], LIMBS[0])
+    }};
+}
+
+/// Build a 256 bits `Scalar` from 64 big endian hexadecimal digits at compile time
+///
+/// The value is not reduced % N, like with `Scalar::new`. A wrong number of
+/// digits fails to compile
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let n = scalar!("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+///
+/// assert_eq!(n, Scalar::GROUP_ORDER);
+/// ```
+#[macro_export]
+macro_rules! scalar {
+    ($hex:literal) => {{
+        const LIMBS: [u64; 4] = $crate::hex_limbs::<4>($hex);
+        $crate::Scalar::new(LIMBS[3], LIMBS[2], LIMBS[1], LIMBS[0])
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scalar::{Scalar, N};
+
+    #[test]
+    fn it_builds_i320_from_hex() {
+        let n = i320!(
+            "0000000000000000FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"
+        );
+        let minus_one = i320!(
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+        let small = i320!(
+            "0000000000000000000000000000000000000000000000000000000000000000000000000000002a"
+        );
+
+        assert_eq!(n, N);
+        assert_eq!(minus_one, Scalar::ZERO - Scalar::ONE);
+        assert_eq!(small, Scalar::from_u64(0x2a));
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn it_builds_field_elements_and_scalars_from_hex() {
+        use crate::field::El;
+
+        let p_1 = fe!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2E");
+        let small = fe!("000000000000000000000000000000000000000000000000000000000000002a");
+        let n = scalar!("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let two_128 = scalar!("0000000000000000000000000000000100000000000000000000000000000000");
+
+        assert_eq!(p_1 + El::ONE, El::ZERO);
+        assert_eq!(small, El::from_u64(0x2a));
+        assert_eq!(n, N);
+        assert_eq!(two_128, Scalar::new(0, 1, 0, 0));
+    }
+}
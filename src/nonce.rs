@@ -0,0 +1,71 @@
+use crate::drbg::HmacDrbg;
+use crate::scalar::Scalar;
+
+/// Generate the nonce k of an ECDSA signature
+///
+/// The nonce must be in [1, N - 1] and must never be reused with a different
+/// hash, otherwise the secret key can be recovered from the signatures.
+pub trait NonceFunction {
+    fn generate(
+        &self,
+        secret_key: &[u8; 32],
+        msg_hash: &[u8; 32],
+        algo: &[u8],
+        data: &[u8; 32],
+    ) -> Scalar;
+}
+
+/// Deterministic nonce from RFC 6979 with HMAC-SHA256
+///
+/// data is added to the HMAC input as extra entropy unless it's all zeros, and
+/// so is algo unless it's empty. Without them this is the plain RFC 6979 nonce.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RFC6979NonceFunction;
+
+impl NonceFunction for RFC6979NonceFunction {
+    fn generate(
+        &self,
+        secret_key: &[u8; 32],
+        msg_hash: &[u8; 32],
+        algo: &[u8],
+        data: &[u8; 32],
+    ) -> Scalar {
+        // extra = data || algo, given to the DRBG as personalization
+        let mut extra = Vec::with_capacity(32 + algo.len());
+
+        if data.iter().any(|b| *b != 0) {
+            extra.extend_from_slice(data);
+        }
+        extra.extend_from_slice(algo);
+
+        let mut drbg = HmacDrbg::new(secret_key, msg_hash, &extra);
+        let mut k = [0u8; 32];
+
+        loop {
+            drbg.generate(&mut k);
+
+            let res = Scalar::from_bytes(&k);
+            if !res.is_zero() && res.get_overflow() == 0 {
+                return res;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hmac::hash256;
+
+    #[test]
+    fn it_adds_extra_data_to_rfc6979() {
+        let secret = hash256(b"n00b");
+        let z = hash256(b"Hello World");
+        let f = RFC6979NonceFunction;
+
+        let k = f.generate(&secret, &z, &[], &[0u8; 32]);
+        assert_eq!(k, f.generate(&secret, &z, &[], &[0u8; 32]));
+        assert_ne!(k, f.generate(&secret, &z, &[], &[0x01; 32]));
+        assert_ne!(k, f.generate(&secret, &z, b"algo", &[0u8; 32]));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::ecc::{Pt, B, G, INFINITY};
+use crate::field::El;
+use crate::hash_to_curve::hash_to_curve_tai;
+use crate::scalar::Scalar;
+
+const GENERATOR_PREFIX: &[u8] = b"estel_secp256k1/generator";
+
+/// Message and domain separation tag hashed with `hash_to_curve` to get H
+pub const GENERATOR_H_MSG: &[u8] = b"secp256k1 generator H";
+pub const GENERATOR_H_DST: &[u8] = b"estel_secp256k1-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+
+const H_X: El = fe!("2f38da1d07240859afb602e8df1720000afe1e12f1a17fc83f10b967f0b973d3");
+
+const H_Y: El = fe!("e592aa39129bec3c660e7264baaff27c3ff56af3b3cb6180bd318313f5281260");
+
+/// Second generator for Pedersen commitments, nobody knows its discrete log to base G
+///
+/// This is `hash_to_curve(GENERATOR_H_MSG, GENERATOR_H_DST)` with the RFC 9380
+/// secp256k1_XMD:SHA-256_SSWU_RO_ suite, precomputed so that it can be a const
+pub const GENERATOR_H: Pt = Pt::new(H_X, H_Y);
+
+/// Check that H satisfies y^2 = x^3 + 7
+// Rust 1.56 does not count the call in the array length below as a use
+#[allow(dead_code)]
+const fn verify_generator_h() -> bool {
+    let y2 = H_Y.mul_fe(&H_Y);
+    let x3_b = H_X.mul_fe(&H_X).mul_add(&H_X, &B);
+
+    y2.equals(&x3_b)
+}
+
+// Fails to compile with a length mismatch if H is not on the curve
+const _: [(); 1] = [(); verify_generator_h() as usize];
+
+/// Derive a generator from a tag, nobody knows its discrete log to base G
+///
+/// e.g. `let h = hash_to_generator(b"H");`
+pub fn hash_to_generator(tag: &[u8]) -> Pt {
+    hash_to_curve_tai(GENERATOR_PREFIX, tag)
+}
+
+/// Pedersen commitment C = v * G + r * H to a value v with a blinding factor r
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PedersenCommitment(pub Pt);
+
+impl PedersenCommitment {
+    /// Commit to a value with a blinding factor in [0, N)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use estel_secp256k1::*;
+    ///
+    /// let h = hash_to_generator(b"H");
+    /// let blinding = Scalar::from_bytes(&hash256("the force".as_bytes()));
+    /// let c = PedersenCommitment::commit(42, &blinding, &h);
+    /// ```
+    pub fn commit(value: u64, blinding: &Scalar, h: &Pt) -> Self {
+        Self(G * Scalar::from_u64(value) + *h * blinding)
+    }
+
+    /// Check that the commitments add up to the excess commitment
+    ///
+    /// Since commitments are homomorphic, this holds when both the values and
+    /// the blinding factors add up (% N)
+    #[must_use]
+    pub fn verify_sum(commitments: &[PedersenCommitment], excess: &PedersenCommitment) -> bool {
+        let mut sum = INFINITY;
+
+        for c in commitments {
+            sum.add_inner(&c.0);
+        }
+        sum == excess.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::hash_to_curve;
+    use crate::scalar::N;
+
+    #[test]
+    fn it_derives_generator_h() {
+        assert_eq!(hash_to_curve(GENERATOR_H_MSG, GENERATOR_H_DST), GENERATOR_H);
+        assert_ne!(GENERATOR_H, G);
+    }
+
+    #[test]
+    fn it_verifies_a_sum_of_commitments() {
+        let h = hash_to_generator(b"H");
+        let (r1, r2) = (Scalar::from_u64(0x1234), Scalar::from_u64(0x5678));
+        let mut r = r1 + r2;
+        r.normalize(&N);
+
+        let c1 = PedersenCommitment::commit(30, &r1, &h);
+        let c2 = PedersenCommitment::commit(12, &r2, &h);
+        let excess = PedersenCommitment::commit(42, &r, &h);
+
+        assert!(PedersenCommitment::verify_sum(&[c1, c2], &excess));
+        assert!(!PedersenCommitment::verify_sum(&[c1], &excess));
+        let wrong = PedersenCommitment::commit(43, &r, &h);
+        assert!(!PedersenCommitment::verify_sum(&[c1, c2], &wrong));
+        assert!(PedersenCommitment::verify_sum(
+            &[],
+            &PedersenCommitment(INFINITY)
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::hash_to_curve::hash_to_curve_tai;
+use crate::hmac::sha256;
+use crate::key::{PrivateKey, PublicKey};
+use crate::scalar::Scalar;
+
+const PRF_PREFIX: &[u8] = b"estel_secp256k1/prf";
+
+/// Pseudorandom function keyed with a private key
+///
+/// The output is sha256(ECDH(key, H(input))) with H a hash to the curve,
+/// e.g. to derive scalars deterministically from a path in a tree
+#[derive(Clone)]
+pub struct Prf {
+    key: PrivateKey,
+}
+
+impl Prf {
+    /// Create a PRF from a private key
+    pub fn new(key: PrivateKey) -> Self {
+        Self { key }
+    }
+
+    /// Evaluate the PRF on an input, the output is reduced % N
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use estel_secp256k1::*;
+    ///
+    /// let secret = Scalar::from_bytes(&hash256("the force".as_bytes()));
+    /// let prf = Prf::new(PrivateKey::new(secret));
+    /// let child = prf.eval("m/0/1".as_bytes());
+    /// ```
+    pub fn eval(&self, input: &[u8]) -> Scalar {
+        let h = hash_to_curve_tai(PRF_PREFIX, input);
+        let shared = h * self.key.secret();
+        let mut pk = PublicKey::from_coords(shared.x, shared.y);
+        let mut r = Scalar::from_bytes(&sha256(&pk.serialize_sec_compressed()));
+
+        r.reduce(r.get_overflow());
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_evaluates_a_prf() {
+        let prf1 = Prf::new(PrivateKey::new(Scalar::from_u64(0x2a)));
+        let prf2 = Prf::new(PrivateKey::new(Scalar::from_u64(0x2b)));
+
+        assert_eq!(prf1.eval(b"m/0"), prf1.eval(b"m/0"));
+        assert_ne!(prf1.eval(b"m/0"), prf1.eval(b"m/1"));
+        assert_ne!(prf1.eval(b"m/0"), prf2.eval(b"m/0"));
+    }
+}
@@ -0,0 +1,59 @@
+use rand_core::{CryptoRng, RngCore};
+
+use crate::scalar::Scalar;
+
+/// Source of cryptographically secure random bytes
+///
+/// It is implemented for every `rand_core` generator marked as `CryptoRng`,
+/// and can be implemented directly for generators that don't use `rand_core`
+pub trait SecureRandom {
+    fn fill_bytes(&mut self, dest: &mut [u8]);
+}
+
+impl<R: CryptoRng + RngCore> SecureRandom for R {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        RngCore::fill_bytes(self, dest)
+    }
+}
+
+/// Draw a scalar in [1, N) from a random number generator
+pub(crate) fn random_scalar(rng: &mut impl SecureRandom) -> Scalar {
+    let mut b = [0u8; 32];
+
+    loop {
+        rng.fill_bytes(&mut b);
+        let k = Scalar::from_bytes(&b);
+        if !k.is_zero() && k.get_overflow() == 0 {
+            return k;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hmac::sha256;
+
+    /// Generator which doesn't implement the rand_core traits, not secure
+    struct HashChain([u8; 32]);
+
+    impl SecureRandom for HashChain {
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(32) {
+                self.0 = sha256(&self.0);
+                chunk.copy_from_slice(&self.0[..chunk.len()]);
+            }
+        }
+    }
+
+    #[test]
+    fn it_draws_scalars_from_any_generator() {
+        let mut rng = HashChain([0u8; 32]);
+        let k1 = random_scalar(&mut rng);
+        let k2 = random_scalar(&mut rng);
+
+        assert_eq!(k1, Scalar::from_bytes(&sha256(&[0u8; 32])));
+        assert_ne!(k1, k2);
+        assert!(k2.is_positive() && k2.get_overflow() == 0);
+    }
+}
@@ -1,9 +1,12 @@
 use std::cmp::Ordering;
+use std::fmt;
 use std::mem;
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
 
-#[cfg(debug_assertions)]
-use std::fmt;
+use crate::error::Error;
 
 /// Order of the secp256k1 group
 const SECP256K1_N_0: u64 = 0xbfd25e8cd0364141u64;
@@ -15,15 +18,51 @@ const SECP256K1_N_3: u64 = 0xffffffffffffffffu64;
 const SECP256K1_NI_0: u64 = 0x402da1732fc9bebfu64;
 const SECP256K1_NI_1: u64 = 0x4551231950b75fc4u64;
 
-const N: Scalar = Scalar::new(SECP256K1_N_3, SECP256K1_N_2, SECP256K1_N_1, SECP256K1_N_0);
+pub(crate) const N: Scalar =
+    Scalar::new(SECP256K1_N_3, SECP256K1_N_2, SECP256K1_N_1, SECP256K1_N_0);
+
+/// Cube root of unity % N, used by the secp256k1 endomorphism
+///
+/// LAMBDA * P is (BETA * P.x, P.y), see [`BETA`](crate::BETA), so
+/// multiplying a scalar with LAMBDA % N maps to a cheap operation on points
+pub const LAMBDA: Scalar =
+    scalar!("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72");
+
+/// N - LAMBDA
+const MINUS_LAMBDA: Scalar =
+    scalar!("ac9c52b33fa3cf1f5ad9e3fd77ed9ba4a880b9fc8ec739c2e0cfc810b51283cf");
+
+/// (N - 1) / 2
+const N_HALF: Scalar = scalar!("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0");
+
+/// -b1 and -b2 % N of the lattice basis used to split scalars (GLV)
+const GLV_MINUS_B1: Scalar =
+    scalar!("00000000000000000000000000000000e4437ed6010e88286f547fa90abfe4c3");
+const GLV_MINUS_B2: Scalar =
+    scalar!("fffffffffffffffffffffffffffffffe8a280ac50774346dd765cda83db1562c");
+
+/// round(2^384 * b2 / N) and round(2^384 * -b1 / N)
+const GLV_G1: Scalar = scalar!("3086d221a7d46bcde86c90e49284eb153daa8a1471e8ca7fe893209a45dbb031");
+const GLV_G2: Scalar = scalar!("e4437ed6010e88286f547fa90abfe4c4221208ac9df506c61571b4ae8ac47f71");
 
 /// Represent 256 bits numbers with support for sign and carry
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Scalar {
     pub d: [u64; 5],
 }
 
 impl Scalar {
+    pub const ZERO: Scalar = Scalar::from_u64(0);
+    pub const ONE: Scalar = Scalar::from_u64(1);
+    /// Order N of the secp256k1 group
+    pub const GROUP_ORDER: Scalar = N;
+    /// Largest value, 2^319 - 1
+    pub const MAX: Scalar = Scalar {
+        d: [u64::MAX, u64::MAX, u64::MAX, u64::MAX, u64::MAX >> 1],
+    };
+    /// Smallest value, -2^319
+    pub const MIN: Scalar = Scalar { d: [0, 0, 0, 0, 1 << 63] };
+
     /// Create a new scalar with d3, d2, d1 and d0 its limbs
     pub const fn new(d3: u64, d2: u64, d1: u64, d0: u64) -> Self {
         Self { d: [d0, d1, d2, d3, 0] }
@@ -121,14 +160,192 @@ impl Scalar {
         b
     }
 
+    /// Convert bytes (little endian) to scalar
+    pub fn from_le_bytes(b: &[u8; 32]) -> Self {
+        let mut be = *b;
+
+        be.reverse();
+        Self::from_bytes(&be)
+    }
+
+    /// Convert scalar to bytes in little endian
+    /// Assuming it is normalized e.g. 256 bits
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut b = self.to_bytes();
+
+        b.reverse();
+        b
+    }
+
+    /// Convert up to 64 bytes (big endian) to a scalar reduced % N
+    ///
+    /// Unlike [`Scalar::from_bytes`], the value can be as large as 2^512 - 1,
+    /// e.g. a 64 bytes hash, and any input gives a valid scalar. An empty
+    /// input is zero
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input is longer than 64 bytes
+    pub fn from_be_bytes_mod_order(bytes: &[u8]) -> Self {
+        assert!(bytes.len() <= 64);
+
+        let mut buf = [0u8; 64];
+        buf[64 - bytes.len()..].copy_from_slice(bytes);
+
+        let mut r = [0u64; 8];
+        for (i, chunk) in buf.rchunks(8).enumerate() {
+            r[i] = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        let mut res = Self::ZERO;
+        res.reduce512(&r);
+        res
+    }
+
+    /// Format the 320 bits as 80 lowercase hexadecimal digits, without prefix
+    ///
+    /// The sign limb comes first, like in the `Debug` output
+    pub fn to_hex_string(&self) -> String {
+        format!(
+            "{:016x}{:016x}{:016x}{:016x}{:016x}",
+            self.d[4], self.d[3], self.d[2], self.d[1], self.d[0]
+        )
+    }
+
+    /// Parse 1 to 80 hexadecimal digits, without prefix, left padded with zeros
+    ///
+    /// This is the inverse of [`Scalar::to_hex_string`]. Unlike `FromStr`, the
+    /// value is not checked against N
+    pub fn from_hex_str(s: &str) -> Result<Self, Error> {
+        if s.is_empty() || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::InvalidHex);
+        }
+        if s.len() > 80 {
+            return Err(Error::OutOfRange);
+        }
+
+        let mut d = [0u64; 5];
+        for (i, chunk) in s.as_bytes().rchunks(16).enumerate() {
+            // only ascii hex digits, the chunk is valid utf-8
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            d[i] = u64::from_str_radix(chunk, 16).unwrap();
+        }
+        Ok(Self { d })
+    }
+
+    #[must_use]
     pub fn is_even(&self) -> bool {
         self.d[0] & 0x1 == 0x0
     }
 
+    #[must_use]
     pub fn is_zero(&self) -> bool {
         (self.d[0] | self.d[1] | self.d[2] | self.d[3] | self.d[4]) == 0
     }
 
+    /// Check if the scalar is negative, the sign is the most significant bit of d[4]
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.d[4] >> 63 != 0
+    }
+
+    /// Check if the scalar is strictly positive
+    #[must_use]
+    pub fn is_positive(&self) -> bool {
+        !self.is_negative() && !self.is_zero()
+    }
+
+    /// Count the leading zero bits of the 320 bits limbs, from the most significant bit of d[4]
+    ///
+    /// A negative scalar has none, and zero has 320
+    #[must_use]
+    pub fn leading_zeros(&self) -> u32 {
+        let mut n = 0;
+
+        for limb in self.d.iter().rev() {
+            n += limb.leading_zeros();
+            if *limb != 0 {
+                break;
+            }
+        }
+        n
+    }
+
+    /// Count the trailing zero bits of the 320 bits limbs, from the least significant bit of d[0]
+    ///
+    /// Zero has 320
+    #[must_use]
+    pub fn trailing_zeros(&self) -> u32 {
+        let mut n = 0;
+
+        for limb in self.d.iter() {
+            n += limb.trailing_zeros();
+            if *limb != 0 {
+                break;
+            }
+        }
+        n
+    }
+
+    /// Iterate over the 256 bits of the scalar, starting from the most significant
+    pub fn bits(&self) -> impl Iterator<Item = bool> {
+        let d = self.d;
+
+        (0..256)
+            .rev()
+            .map(move |i| (d[i / 64] >> (i % 64)) & 0x1 == 0x1)
+    }
+
+    /// Iterate over the bits of the scalar, starting from the most significant 1
+    pub fn non_zero_bits_msb(&self) -> impl Iterator<Item = bool> {
+        self.bits().skip_while(|b| !b)
+    }
+
+    /// Calculate the non-adjacent form of the scalar, least significant digit first
+    ///
+    /// Digits are -1, 0 or 1 and no two consecutive digits are non-zero
+    pub fn to_naf(&self) -> Vec<i8> {
+        self.to_wnaf(2)
+    }
+
+    /// Calculate the width-w non-adjacent form of the scalar, least significant digit first
+    ///
+    /// Digits are odd in ]-2^(w-1), 2^(w-1)[ or 0, and any w consecutive digits
+    /// contain at most one non-zero digit
+    ///
+    /// # Panics
+    ///
+    /// Panics if w is not in [2, 8], the digits must fit in an i8
+    pub fn to_wnaf(&self, w: u32) -> Vec<i8> {
+        assert!((2..=8).contains(&w));
+
+        let mask = (1u64 << w) - 1;
+        let half = 1i64 << (w - 1);
+        let mut k = *self;
+        let mut res = Vec::with_capacity(257);
+
+        while !k.is_zero() {
+            let mut digit = 0i64;
+
+            if !k.is_even() {
+                digit = (k.d[0] & mask) as i64;
+                if digit >= half {
+                    digit -= 1 << w;
+                }
+                // k - digit is a multiple of 2^w
+                if digit > 0 {
+                    k -= Scalar::from_u64(digit as u64);
+                } else {
+                    k += Scalar::from_u64(-digit as u64);
+                }
+            }
+            res.push(digit as i8);
+            k.div2();
+        }
+
+        res
+    }
+
     pub fn div2(&mut self) {
         let mut t: u64;
 
@@ -153,15 +370,10 @@ impl Scalar {
 
     /// Normalize a scalar modulo m
     pub fn normalize(&mut self, m: &Self) {
-        let s = self.d[4] >> 63;
-
-        if s > 0 {
-            // self < 0
+        if self.is_negative() {
             self.add_assign(m);
-            let s2 = self.d[4] >> 63;
 
-            if s2 > 0 {
-                // self < 0
+            if self.is_negative() {
                 self.add_assign(m);
             }
         } else if *self >= *m {
@@ -194,6 +406,284 @@ impl Scalar {
         y.normalize(m);
         *self = y;
     }
+
+    /// Calculate the inverse of the scalar % m with Lehmer's euclidean algorithm
+    ///
+    /// The scalar must be in [0, m). Quotients are guessed from the 63 most
+    /// significant bits of the remainders so that most steps only need single
+    /// word arithmetic (Knuth, TAOCP vol. 2, algorithm 4.5.2L)
+    pub fn modinv_lehmer(&mut self, m: &Self) {
+        debug_assert!(*self < *m);
+
+        // remainders a > b and the magnitudes of their cofactors: cofactor
+        // signs alternate, the one of a is negative when neg is set
+        let mut a = *m;
+        let mut b = *self;
+        let mut ta = Self::from_u64(0);
+        let mut tb = Self::from_u64(1);
+        let mut neg = true;
+
+        while !b.is_zero() {
+            let shift = a.bit_len().saturating_sub(63);
+            let mut ah = a.low_u64_from(shift) as i128;
+            let mut bh = b.low_u64_from(shift) as i128;
+            let (mut x0, mut x1, mut y0, mut y1) = (1i128, 0i128, 0i128, 1i128);
+            let mut steps = 0;
+
+            while bh + y0 > 0 && bh + y1 > 0 {
+                let q = (ah + x0) / (bh + y0);
+
+                if q != (ah + x1) / (bh + y1) {
+                    break;
+                }
+                let t = x0 - q * y0;
+                x0 = y0;
+                y0 = t;
+                let t = x1 - q * y1;
+                x1 = y1;
+                y1 = t;
+                let t = ah - q * bh;
+                ah = bh;
+                bh = t;
+                steps += 1;
+            }
+
+            if steps == 0 {
+                // the quotient could not be guessed, divide a by b
+                let (r, t) = Self::euclid_step(&a, &b, &ta, &tb);
+
+                a = b;
+                b = r;
+                ta = tb;
+                tb = t;
+                neg = !neg;
+            } else {
+                let (na, nb) = (
+                    Self::combine_wrapping(&a, x0, &b, x1),
+                    Self::combine_wrapping(&a, y0, &b, y1),
+                );
+                let (nta, ntb) = (
+                    ta.mul_u64_wrapping(x0.unsigned_abs() as u64)
+                        .add_wrapping(&tb.mul_u64_wrapping(x1.unsigned_abs() as u64)),
+                    ta.mul_u64_wrapping(y0.unsigned_abs() as u64)
+                        .add_wrapping(&tb.mul_u64_wrapping(y1.unsigned_abs() as u64)),
+                );
+                a = na;
+                b = nb;
+                ta = nta;
+                tb = ntb;
+                neg ^= steps & 1 != 0;
+            }
+        }
+        if neg && !ta.is_zero() {
+            *self = *m - ta;
+        } else {
+            *self = ta;
+        }
+    }
+
+    /// Calculate the inverse of the scalar % m with the extended euclidean algorithm
+    ///
+    /// The scalar must be in [0, m). Each step divides the remainders with a
+    /// shift and subtract long division, which costs one iteration per bit of
+    /// the quotient. The binary GCD always needs about 2 * 256 shift steps, so
+    /// this is faster when the quotients are large, e.g. for small inputs, and
+    /// slower for typical 256 bits inputs where most quotients are 1 or 2
+    pub fn modinv_extended_euclidean(&mut self, m: &Self) {
+        debug_assert!(*self < *m);
+
+        // same cofactor sign convention as in modinv_lehmer
+        let mut a = *m;
+        let mut b = *self;
+        let mut ta = Self::from_u64(0);
+        let mut tb = Self::from_u64(1);
+        let mut neg = true;
+
+        while !b.is_zero() {
+            let (r, t) = Self::euclid_step(&a, &b, &ta, &tb);
+
+            a = b;
+            b = r;
+            ta = tb;
+            tb = t;
+            neg = !neg;
+        }
+        if neg && !ta.is_zero() {
+            *self = *m - ta;
+        } else {
+            *self = ta;
+        }
+    }
+
+    /// Calculate the inverse of the scalar % m with the given algorithm
+    pub fn modinv_inner_with(&mut self, m: &Self, strategy: ModInvStrategy) {
+        match strategy {
+            ModInvStrategy::BinaryGcd => self.modinv_inner_from(m),
+            ModInvStrategy::Lehmer => self.modinv_lehmer(m),
+            ModInvStrategy::ExtendedEuclidean => self.modinv_extended_euclidean(m),
+        }
+    }
+
+    /// Divide a by b with q the quotient, return (a - q * b, ta + q * tb)
+    ///
+    /// a >= b > 0 and the cofactor magnitudes ta and tb are positive
+    fn euclid_step(a: &Self, b: &Self, ta: &Self, tb: &Self) -> (Self, Self) {
+        let mut k = a.bit_len() - b.bit_len();
+        let mut r = *a;
+        let mut t = *ta;
+
+        loop {
+            let bk = b.shl_wrapping(k);
+
+            if r >= bk {
+                r -= bk;
+                t = t.add_wrapping(&tb.shl_wrapping(k));
+            }
+            if k == 0 {
+                break;
+            }
+            k -= 1;
+        }
+        (r, t)
+    }
+
+    /// Number of significant bits of a positive scalar
+    fn bit_len(&self) -> u32 {
+        for i in (0..4).rev() {
+            if self.d[i] != 0 {
+                return 64 * i as u32 + 64 - self.d[i].leading_zeros();
+            }
+        }
+        0
+    }
+
+    /// Low 64 bits of a positive scalar shifted right by n bits
+    fn low_u64_from(&self, n: u32) -> u64 {
+        let (i, o) = ((n / 64) as usize, n % 64);
+        let lo = self.d[i] >> o;
+
+        if o > 0 && i < 3 {
+            lo | (self.d[i + 1] << (64 - o))
+        } else {
+            lo
+        }
+    }
+
+    /// Shift a positive scalar left by n < 256 bits % 2^256
+    fn shl_wrapping(&self, n: u32) -> Self {
+        let (s, o) = ((n / 64) as usize, n % 64);
+        let mut r = Self::from_u64(0);
+
+        for i in s..4 {
+            r.d[i] = self.d[i - s] << o;
+            if o > 0 && i > s {
+                r.d[i] |= self.d[i - s - 1] >> (64 - o);
+            }
+        }
+        r
+    }
+
+    /// Add 2 positive scalars % 2^256
+    fn add_wrapping(&self, b: &Self) -> Self {
+        let mut r = *self + *b;
+
+        r.d[4] = 0;
+        r
+    }
+
+    /// Multiply a positive scalar with a word % 2^256
+    fn mul_u64_wrapping(&self, b: u64) -> Self {
+        let mut r = Self::from_u64(0);
+        let mut t: u128 = 0;
+
+        for i in 0..4 {
+            t += self.d[i] as u128 * b as u128;
+            r.d[i] = t as u64;
+            t >>= 64;
+        }
+        r
+    }
+
+    /// Calculate x * a + y * b % 2^256 when x and y have opposite signs
+    fn combine_wrapping(a: &Self, x: i128, b: &Self, y: i128) -> Self {
+        let xa = a.mul_u64_wrapping(x.unsigned_abs() as u64);
+        let yb = b.mul_u64_wrapping(y.unsigned_abs() as u64);
+        let mut r = if x < 0 || y > 0 { yb - xa } else { xa - yb };
+
+        r.d[4] = 0;
+        r
+    }
+}
+
+/// Parse a positive number of at most 256 bits written in the given radix
+pub(crate) fn parse_u256(s: &str, radix: u32) -> Result<Scalar, Error> {
+    if s.is_empty() {
+        return Err(Error::InvalidNumber);
+    }
+
+    let mut n = Scalar::ZERO;
+    for c in s.chars() {
+        let digit = c.to_digit(radix).ok_or(Error::InvalidNumber)?;
+
+        n = n.mul_small(radix).0 + Scalar::from_u64(digit as u64);
+        if n.d[4] != 0 {
+            return Err(Error::OutOfRange);
+        }
+    }
+
+    Ok(n)
+}
+
+/// Parse a number lower than 2^256 from a string, see `Scalar::from_str`
+///
+/// Strings starting with `0x` and strings with a digit in [a-f] are parsed as
+/// hexadecimal, anything else as decimal
+pub(crate) fn parse_u256_str(s: &str) -> Result<Scalar, Error> {
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        parse_u256(hex, 16)
+    } else if s.bytes().any(|c| matches!(c, b'a'..=b'f' | b'A'..=b'F')) {
+        parse_u256(s, 16)
+    } else {
+        parse_u256(s, 10)
+    }
+}
+
+/// Calculate (N - n) % N for n in [0, N)
+#[cfg(feature = "secp256k1")]
+pub(crate) fn negate_mod_n(n: &Scalar) -> Scalar {
+    if n.is_zero() {
+        *n
+    } else {
+        N - *n
+    }
+}
+
+/// Algorithm used to calculate a modular inverse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModInvStrategy {
+    /// Binary extended GCD, see `Scalar::modinv_inner_from`
+    BinaryGcd,
+    /// Lehmer's euclidean algorithm, see `Scalar::modinv_lehmer`
+    Lehmer,
+    /// Extended euclidean algorithm, see `Scalar::modinv_extended_euclidean`
+    ExtendedEuclidean,
+}
+
+/// 32 bytes big endian encoding of a scalar, left padded with zeros
+///
+/// Small values, e.g. 128 bits scalars, always take the full 32 bytes, as
+/// expected by BIP-340 nonce derivation or FROST
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaddedScalar(pub [u8; 32]);
+
+impl PaddedScalar {
+    pub fn from_scalar(s: &Scalar) -> Self {
+        Self(s.to_bytes())
+    }
+
+    pub fn to_scalar(&self) -> Scalar {
+        Scalar::from_bytes(&self.0)
+    }
 }
 
 macro_rules! define_ops {
@@ -356,9 +846,7 @@ impl Scalar {
         let mut c: u128;
         let (n0, n1, n2, n3) = (r[4], r[5], r[6], r[7]);
         let (m0, m1, m2, m3, m4, m5): (u64, u64, u64, u64, u64, u64);
-        let m6: u32;
         let (p0, p1, p2, p3): (u64, u64, u64, u64);
-        let p4: u32;
 
         c0 = r[0];
         c1 = 0;
@@ -386,7 +874,7 @@ impl Scalar {
         m4 = extract!();
         sumadd_fast!(n3);
         m5 = extract_fast!();
-        m6 = c0 as u32;
+        let m6 = c0 as u32;
 
         c0 = m0;
         c1 = 0;
@@ -406,7 +894,7 @@ impl Scalar {
         muladd_fast!(m6, SECP256K1_NI_1);
         sumadd_fast!(m5);
         p3 = extract_fast!();
-        p4 = c0 as u32 + m6 as u32;
+        let p4 = c0 as u32 + m6;
         debug_assert!(p4 <= 2);
 
         c = p0 as u128 + SECP256K1_NI_0 as u128 * p4 as u128;
@@ -463,13 +951,210 @@ impl Scalar {
         res
     }
 
+    /// Calculate (self * a + b) % N with a single reduction, b must be lower than 2^256
+    pub fn mul_add(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        debug_assert_eq!(b.d[4], 0);
+
+        let mut r = self.mul512(a);
+        let mut t: u128 = 0;
+        let mut res = *self;
+
+        for (i, ri) in r.iter_mut().enumerate() {
+            t += *ri as u128 + if i < 4 { b.d[i] as u128 } else { 0 };
+            *ri = t as u64;
+            t >>= 64;
+        }
+        res.reduce512(&r);
+
+        res
+    }
+
+    /// Calculate round(self * g / 2^384)
+    fn mul_shift_384(&self, g: &Scalar) -> Scalar {
+        let r = self.mul512(g);
+        let t = r[6] as u128 + (r[5] >> 63) as u128;
+
+        Scalar::new(0, 0, r[7] + (t >> 64) as u64, t as u64)
+    }
+
+    /// Split k % N into k1 + k2 * LAMBDA with k1 and k2 lower than 2^128 (GLV)
+    ///
+    /// The half length scalars are returned as absolute values with their
+    /// signs, k = (-1)^neg1 * k1 + (-1)^neg2 * k2 * LAMBDA % N
+    pub fn split_endomorphism(k: &Scalar) -> (Scalar, Scalar, bool, bool) {
+        debug_assert_eq!(k.get_overflow(), 0);
+
+        let c1 = k.mul_shift_384(&GLV_G1);
+        let c2 = k.mul_shift_384(&GLV_G2);
+
+        // r2 = c1 * -b1 + c2 * -b2, r1 = k - r2 * LAMBDA
+        let r2 = c2.mul_add(&GLV_MINUS_B2, &c1.mulmod(&GLV_MINUS_B1));
+        let r1 = r2.mul_add(&MINUS_LAMBDA, k);
+
+        let neg1 = r1 > N_HALF;
+        let neg2 = r2 > N_HALF;
+        let k1 = if neg1 { N - r1 } else { r1 };
+        let k2 = if neg2 { N - r2 } else { r2 };
+
+        (k1, k2, neg1, neg2)
+    }
+
     /// Scalar inverse % N
     pub fn modinv_inner(&mut self) {
         self.modinv_inner_from(&N)
     }
+
+    /// Multiply the 320 bits of self, read as unsigned, with a word
+    ///
+    /// Return the low 320 bits and the carry, the bits from 320. Field
+    /// elements have their own small multiplication, `El * u64`
+    pub fn mul_small(&self, k: u32) -> (Self, u64) {
+        let mut r = Self::ZERO;
+        let mut t: u128 = 0;
+
+        for i in 0..5 {
+            t += self.d[i] as u128 * k as u128;
+            r.d[i] = t as u64;
+            t >>= 64;
+        }
+        (r, t as u64)
+    }
+
+    /// Calculate self + rhs % 2^320
+    ///
+    /// The carry out of d[4] is dropped, so MAX + 1 wraps to MIN, like the
+    /// wrapping_add of Rust integers. The `+` operators call this function
+    pub fn wrapping_add(&self, rhs: &Self) -> Self {
+        let mut r = *self;
+        let mut t: u128;
+
+        t = (r.d[0] as u128).wrapping_add(rhs.d[0] as u128);
+        r.d[0] = t as u64;
+        t >>= 64;
+
+        t = (r.d[1] as u128).wrapping_add(t + rhs.d[1] as u128);
+        r.d[1] = t as u64;
+        t >>= 64;
+
+        t = (r.d[2] as u128).wrapping_add(t + rhs.d[2] as u128);
+        r.d[2] = t as u64;
+        t >>= 64;
+
+        t = (r.d[3] as u128).wrapping_add(t + rhs.d[3] as u128);
+        r.d[3] = t as u64;
+        t >>= 64;
+
+        t = (r.d[4] as u128).wrapping_add(t + rhs.d[4] as u128);
+        r.d[4] = t as u64;
+
+        r
+    }
+
+    /// Calculate self + rhs, clamped to [MIN, MAX] instead of wrapping
+    pub fn saturating_add(&self, rhs: &Self) -> Self {
+        let r = self.wrapping_add(rhs);
+
+        // overflow when both signs are the same and the result sign differs
+        if self.is_negative() == rhs.is_negative() && r.is_negative() != self.is_negative() {
+            if self.is_negative() {
+                Self::MIN
+            } else {
+                Self::MAX
+            }
+        } else {
+            r
+        }
+    }
+
+    /// Calculate self - rhs, clamped to [MIN, MAX] instead of wrapping
+    pub fn saturating_sub(&self, rhs: &Self) -> Self {
+        let r = self.wrapping_sub(rhs);
+
+        // overflow when the signs differ and the result sign is the one of rhs
+        if self.is_negative() != rhs.is_negative() && r.is_negative() != self.is_negative() {
+            if self.is_negative() {
+                Self::MIN
+            } else {
+                Self::MAX
+            }
+        } else {
+            r
+        }
+    }
+
+    /// Smallest of a and b, see `Ord` for the signed ordering
+    pub fn min(a: Self, b: Self) -> Self {
+        if b < a {
+            b
+        } else {
+            a
+        }
+    }
+
+    /// Largest of a and b, see `Ord` for the signed ordering
+    pub fn max(a: Self, b: Self) -> Self {
+        if b > a {
+            b
+        } else {
+            a
+        }
+    }
+
+    /// Restrict self to [min, max], min must not be greater than max
+    ///
+    /// self is taken by value so that `n.clamp(&min, &max)` resolves to this
+    /// function rather than to `Ord::clamp`
+    pub fn clamp(self, min: &Self, max: &Self) -> Self {
+        debug_assert!(min <= max);
+
+        if self < *min {
+            *min
+        } else if self > *max {
+            *max
+        } else {
+            self
+        }
+    }
+
+    /// Calculate self - rhs % 2^320
+    ///
+    /// The borrow out of d[4] is dropped, so MIN - 1 wraps to MAX, like the
+    /// wrapping_sub of Rust integers. The `-` operators call this function
+    pub fn wrapping_sub(&self, rhs: &Self) -> Self {
+        let mut r = *self;
+        let mut t: u128;
+
+        // Each limb is subtracted in 128 bits. When it borrows, the result
+        // wraps below 0 and bits 64..128 are all set, otherwise they are all
+        // clear: t >> 64 is either 0 or 2^64 - 1, and & 0x01 turns it into the
+        // borrow 0 or 1 subtracted from the next limb with the rhs limb
+        t = (r.d[0] as u128).wrapping_sub(rhs.d[0] as u128);
+        r.d[0] = t as u64;
+        t >>= 64;
+        t &= 0x01;
+
+        t = (r.d[1] as u128).wrapping_sub(t + rhs.d[1] as u128);
+        r.d[1] = t as u64;
+        t >>= 64;
+        t &= 0x01;
+
+        t = (r.d[2] as u128).wrapping_sub(t + rhs.d[2] as u128);
+        r.d[2] = t as u64;
+        t >>= 64;
+        t &= 0x01;
+
+        t = (r.d[3] as u128).wrapping_sub(t + rhs.d[3] as u128);
+        r.d[3] = t as u64;
+        t >>= 64;
+        t &= 0x01;
+
+        t = (r.d[4] as u128).wrapping_sub(t + rhs.d[4] as u128);
+        r.d[4] = t as u64;
+
+        r
+    }
 }
 
-#[cfg(debug_assertions)]
 impl fmt::Debug for Scalar {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -480,6 +1165,50 @@ impl fmt::Debug for Scalar {
     }
 }
 
+/// 5 limbs of 64 bits, least significant first, the last one holds the sign
+impl From<[u64; 5]> for Scalar {
+    fn from(d: [u64; 5]) -> Self {
+        Self { d }
+    }
+}
+
+impl From<Scalar> for [u64; 5] {
+    fn from(n: Scalar) -> Self {
+        n.d
+    }
+}
+
+/// Read a scalar from 32 bytes big endian, like `Scalar::from_bytes`
+///
+/// Fails with `InvalidBuffer` if the slice is not 32 bytes long
+impl TryFrom<&[u8]> for Scalar {
+    type Error = Error;
+
+    fn try_from(b: &[u8]) -> Result<Self, Error> {
+        let b: &[u8; 32] = b.try_into().or(Err(Error::InvalidBuffer))?;
+
+        Ok(Scalar::from_bytes(b))
+    }
+}
+
+/// Parse a scalar lower than N from a string
+///
+/// Strings starting with `0x` and strings with a digit in [a-f] are parsed as
+/// hexadecimal, anything else as decimal. Hexadecimal strings made of 0-9
+/// only need the `0x` prefix
+impl FromStr for Scalar {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let n = parse_u256_str(s)?;
+
+        if n >= N {
+            return Err(Error::OutOfRange);
+        }
+        Ok(n)
+    }
+}
+
 impl Add<Scalar> for Scalar {
     type Output = Scalar;
 
@@ -491,7 +1220,7 @@ impl Add<Scalar> for Scalar {
     }
 }
 
-impl<'a, 'b> Add<&'a Scalar> for &'b Scalar {
+impl<'a> Add<&'a Scalar> for &Scalar {
     type Output = Scalar;
 
     fn add(self, rhs: &'a Scalar) -> Scalar {
@@ -504,26 +1233,7 @@ impl<'a, 'b> Add<&'a Scalar> for &'b Scalar {
 
 impl<'a> AddAssign<&'a Scalar> for Scalar {
     fn add_assign(&mut self, rhs: &'a Scalar) {
-        let mut t: u128;
-
-        t = (self.d[0] as u128).wrapping_add(rhs.d[0] as u128);
-        self.d[0] = t as u64;
-        t >>= 64;
-
-        t = (self.d[1] as u128).wrapping_add(t + rhs.d[1] as u128);
-        self.d[1] = t as u64;
-        t >>= 64;
-
-        t = (self.d[2] as u128).wrapping_add(t + rhs.d[2] as u128);
-        self.d[2] = t as u64;
-        t >>= 64;
-
-        t = (self.d[3] as u128).wrapping_add(t + rhs.d[3] as u128);
-        self.d[3] = t as u64;
-        t >>= 64;
-
-        t = (self.d[4] as u128).wrapping_add(t + rhs.d[4] as u128);
-        self.d[4] = t as u64;
+        *self = self.wrapping_add(rhs);
     }
 }
 
@@ -544,7 +1254,7 @@ impl Sub<Scalar> for Scalar {
     }
 }
 
-impl<'a, 'b> Sub<&'a Scalar> for &'b Scalar {
+impl<'a> Sub<&'a Scalar> for &Scalar {
     type Output = Scalar;
 
     fn sub(self, rhs: &'a Scalar) -> Scalar {
@@ -557,36 +1267,121 @@ impl<'a, 'b> Sub<&'a Scalar> for &'b Scalar {
 
 impl<'a> SubAssign<&'a Scalar> for Scalar {
     fn sub_assign(&mut self, rhs: &'a Scalar) {
-        let mut t: u128;
+        *self = self.wrapping_sub(rhs);
+    }
+}
 
-        t = (self.d[0] as u128).wrapping_sub(rhs.d[0] as u128);
-        self.d[0] = t as u64;
-        t >>= 64;
-        t &= 0x01;
+impl SubAssign<Scalar> for Scalar {
+    fn sub_assign(&mut self, rhs: Scalar) {
+        self.sub_assign(&rhs)
+    }
+}
 
-        t = (self.d[1] as u128).wrapping_sub(t + rhs.d[1] as u128);
-        self.d[1] = t as u64;
-        t >>= 64;
-        t &= 0x01;
+impl BitAnd<Scalar> for Scalar {
+    type Output = Scalar;
 
-        t = (self.d[2] as u128).wrapping_sub(t + rhs.d[2] as u128);
-        self.d[2] = t as u64;
-        t >>= 64;
-        t &= 0x01;
+    fn bitand(self, rhs: Scalar) -> Scalar {
+        let mut r = self;
 
-        t = (self.d[3] as u128).wrapping_sub(t + rhs.d[3] as u128);
-        self.d[3] = t as u64;
-        t >>= 64;
-        t &= 0x01;
+        r.bitand_assign(&rhs);
+        r
+    }
+}
+
+impl<'a> BitAnd<&'a Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn bitand(self, rhs: &'a Scalar) -> Scalar {
+        let mut r = *self;
 
-        t = (self.d[4] as u128).wrapping_sub(t + rhs.d[4] as u128);
-        self.d[4] = t as u64;
+        r.bitand_assign(rhs);
+        r
     }
 }
 
-impl SubAssign<Scalar> for Scalar {
-    fn sub_assign(&mut self, rhs: Scalar) {
-        self.sub_assign(&rhs)
+impl<'a> BitAndAssign<&'a Scalar> for Scalar {
+    fn bitand_assign(&mut self, rhs: &'a Scalar) {
+        for (a, b) in self.d.iter_mut().zip(rhs.d.iter()) {
+            *a &= b;
+        }
+    }
+}
+
+impl BitAndAssign<Scalar> for Scalar {
+    fn bitand_assign(&mut self, rhs: Scalar) {
+        self.bitand_assign(&rhs)
+    }
+}
+
+impl BitOr<Scalar> for Scalar {
+    type Output = Scalar;
+
+    fn bitor(self, rhs: Scalar) -> Scalar {
+        let mut r = self;
+
+        r.bitor_assign(&rhs);
+        r
+    }
+}
+
+impl<'a> BitOr<&'a Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn bitor(self, rhs: &'a Scalar) -> Scalar {
+        let mut r = *self;
+
+        r.bitor_assign(rhs);
+        r
+    }
+}
+
+impl<'a> BitOrAssign<&'a Scalar> for Scalar {
+    fn bitor_assign(&mut self, rhs: &'a Scalar) {
+        for (a, b) in self.d.iter_mut().zip(rhs.d.iter()) {
+            *a |= b;
+        }
+    }
+}
+
+impl BitOrAssign<Scalar> for Scalar {
+    fn bitor_assign(&mut self, rhs: Scalar) {
+        self.bitor_assign(&rhs)
+    }
+}
+
+impl BitXor<Scalar> for Scalar {
+    type Output = Scalar;
+
+    fn bitxor(self, rhs: Scalar) -> Scalar {
+        let mut r = self;
+
+        r.bitxor_assign(&rhs);
+        r
+    }
+}
+
+impl<'a> BitXor<&'a Scalar> for &Scalar {
+    type Output = Scalar;
+
+    fn bitxor(self, rhs: &'a Scalar) -> Scalar {
+        let mut r = *self;
+
+        r.bitxor_assign(rhs);
+        r
+    }
+}
+
+impl<'a> BitXorAssign<&'a Scalar> for Scalar {
+    fn bitxor_assign(&mut self, rhs: &'a Scalar) {
+        for (a, b) in self.d.iter_mut().zip(rhs.d.iter()) {
+            *a ^= b;
+        }
+    }
+}
+
+impl BitXorAssign<Scalar> for Scalar {
+    fn bitxor_assign(&mut self, rhs: Scalar) {
+        self.bitxor_assign(&rhs)
     }
 }
 
@@ -630,6 +1425,183 @@ impl PartialOrd for Scalar {
 mod tests {
     use super::*;
 
+    #[test]
+    fn it_converts_little_endian_bytes() {
+        let mut b = [0u8; 32];
+        b[0] = 0x41;
+        b[1] = 0x41;
+        b[31] = 0xff;
+
+        let n = Scalar::from_le_bytes(&b);
+        assert_eq!(n, Scalar::new(0xff00000000000000, 0, 0, 0x4141));
+        assert_eq!(n.to_le_bytes(), b);
+        assert_eq!(N.to_le_bytes()[0], 0x41);
+    }
+
+    #[test]
+    fn it_reduces_bytes_mod_order() {
+        let n = Scalar::from_be_bytes_mod_order(&[0xff; 64]);
+        let expected = "0x9d671cd581c69bc5e697f5e45bcd07c6741496c20e7cf878896cf21467d7d13f";
+        assert_eq!(n, expected.parse::<Scalar>().unwrap());
+
+        let b: Vec<u8> = (1..=48).collect();
+        let expected = "0x1259f2ae83a986c3c506758609bd3a765bbb0c1aac771fe2995103d81d694c20";
+        assert_eq!(
+            Scalar::from_be_bytes_mod_order(&b),
+            expected.parse::<Scalar>().unwrap()
+        );
+
+        assert_eq!(Scalar::from_be_bytes_mod_order(&N.to_bytes()), Scalar::ZERO);
+        assert_eq!(Scalar::from_be_bytes_mod_order(&[]), Scalar::ZERO);
+        assert_eq!(
+            Scalar::from_be_bytes_mod_order(&[0x2a]),
+            Scalar::from_u64(0x2a)
+        );
+        let mut b = [0u8; 40];
+        b[8..40].copy_from_slice(&(N + Scalar::from_u64(5)).to_bytes());
+        assert_eq!(Scalar::from_be_bytes_mod_order(&b), Scalar::from_u64(5));
+    }
+
+    #[test]
+    fn it_converts_to_and_from_hex_strings() {
+        let minus_one = Scalar::ZERO - Scalar::ONE;
+        assert_eq!(minus_one.to_hex_string(), "f".repeat(80));
+        assert_eq!(Scalar::from_hex_str(&"f".repeat(80)), Ok(minus_one));
+
+        let hex = N.to_hex_string();
+        assert_eq!(hex.len(), 80);
+        assert_eq!(format!("0x{}", hex), format!("{:?}", N));
+        assert_eq!(Scalar::from_hex_str(&hex), Ok(N));
+        assert_eq!(Scalar::from_hex_str(hex.trim_start_matches('0')), Ok(N));
+
+        assert_eq!(Scalar::from_hex_str("2A"), Ok(Scalar::from_u64(0x2a)));
+        assert_eq!(Scalar::from_hex_str(""), Err(Error::InvalidHex));
+        assert_eq!(Scalar::from_hex_str("0x2a"), Err(Error::InvalidHex));
+        assert_eq!(Scalar::from_hex_str("+2a"), Err(Error::InvalidHex));
+        assert_eq!(
+            Scalar::from_hex_str(&"1".repeat(81)),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn it_converts_from_byte_slices() {
+        let b = N.to_bytes();
+
+        assert_eq!(Scalar::try_from(&b[..]), Ok(N));
+        assert_eq!(Scalar::try_from(&b[1..]), Err(Error::InvalidBuffer));
+        assert_eq!(Scalar::try_from(&[0u8; 33][..]), Err(Error::InvalidBuffer));
+    }
+
+    #[test]
+    fn it_defaults_to_zero() {
+        let a: [Scalar; 4] = Default::default();
+
+        assert_eq!(Scalar::default(), Scalar::ZERO);
+        assert!(a.iter().all(|n| n.is_zero()));
+    }
+
+    #[test]
+    fn it_converts_u64_limbs() {
+        let d = [0x1, 0x2, 0x3, 0x4, 0x0];
+        let n = Scalar::from(d);
+
+        assert_eq!(n, Scalar::new(0x4, 0x3, 0x2, 0x1));
+        assert_eq!(<[u64; 5]>::from(n), d);
+        assert_eq!(<[u64; 5]>::from(Scalar::from_u64(0) - n)[4], u64::MAX);
+    }
+
+    #[test]
+    fn it_parses_scalars() {
+        let n_1 = N - Scalar::ONE;
+
+        assert_eq!("0x2a".parse::<Scalar>().unwrap(), Scalar::from_u64(0x2a));
+        assert_eq!("0X2A".parse::<Scalar>().unwrap(), Scalar::from_u64(0x2a));
+        assert_eq!("42".parse::<Scalar>().unwrap(), Scalar::from_u64(0x2a));
+        assert_eq!("0".parse::<Scalar>().unwrap(), Scalar::ZERO);
+        assert_eq!("2A".parse::<Scalar>().unwrap(), Scalar::from_u64(0x2a));
+        assert_eq!(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140"
+                .parse::<Scalar>()
+                .unwrap(),
+            n_1
+        );
+        assert_eq!(
+            "115792089237316195423570985008687907852837564279074904382605163141518161494336"
+                .parse::<Scalar>()
+                .unwrap(),
+            n_1
+        );
+        // 64 decimal digits, 10^63
+        assert_eq!(
+            format!("1{}", "0".repeat(63)).parse::<Scalar>().unwrap(),
+            (0..63).fold(Scalar::ONE, |n, _| n.mul_small(10).0)
+        );
+        assert_eq!(
+            "0x000000000000000000000000000000000000000000000000000000000000000000002a"
+                .parse::<Scalar>()
+                .unwrap(),
+            Scalar::from_u64(0x2a)
+        );
+    }
+
+    #[test]
+    fn it_cannot_parse_scalars() {
+        let invalid = ["", "0x", "-1", "2g", "0x2g", " 42", "0x0x2a"];
+        for s in invalid.iter() {
+            assert_eq!(s.parse::<Scalar>().unwrap_err(), Error::InvalidNumber);
+        }
+
+        let too_large = [
+            "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            "115792089237316195423570985008687907852837564279074904382605163141518161494337",
+            "0x10000000000000000000000000000000000000000000000000000000000000000",
+            "1000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ];
+        for s in too_large.iter() {
+            assert_eq!(s.parse::<Scalar>().unwrap_err(), Error::OutOfRange);
+        }
+    }
+
+    #[test]
+    fn it_splits_scalars_with_the_endomorphism() {
+        let half = Scalar::new(0, 1, 0, 0);
+        let mut k = Scalar::from_bytes(&[0x5a; 32]);
+
+        for _ in 0..64 {
+            let (k1, k2, neg1, neg2) = Scalar::split_endomorphism(&k);
+            let t1 = if neg1 { N - k1 } else { k1 };
+            let t2 = if neg2 { N - k2 } else { k2 };
+            let mut r = t2.mul_add(&LAMBDA, &t1);
+
+            r.reduce(r.get_overflow());
+            assert_eq!(r, k);
+            assert!(k1 < half && k2 < half);
+
+            // next pseudo random scalar
+            k = k.mul_add(&k, &LAMBDA);
+        }
+
+        assert_eq!(
+            Scalar::split_endomorphism(&(N - Scalar::from_u64(1))),
+            (Scalar::from_u64(1), Scalar::from_u64(0), true, false)
+        );
+        assert_eq!(
+            Scalar::split_endomorphism(&LAMBDA),
+            (Scalar::from_u64(0), Scalar::from_u64(1), false, false)
+        );
+    }
+
+    #[test]
+    fn it_pads_small_scalars() {
+        let n = Scalar::from_u64(0x2a);
+        let p = PaddedScalar::from_scalar(&n);
+
+        assert_eq!(&p.0[0..31], &[0u8; 31][..]);
+        assert_eq!(p.0[31], 0x2a);
+        assert_eq!(p.to_scalar(), n);
+    }
+
     #[test]
     fn it_div2() {
         let mut a = Scalar::new(
@@ -651,6 +1623,90 @@ mod tests {
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn it_counts_leading_and_trailing_zeros() {
+        assert_eq!(Scalar::ZERO.leading_zeros(), 320);
+        assert_eq!(Scalar::ZERO.trailing_zeros(), 320);
+        assert_eq!(Scalar::ONE.leading_zeros(), 319);
+        assert_eq!(Scalar::ONE.trailing_zeros(), 0);
+        assert_eq!(N.leading_zeros(), 64);
+        assert_eq!(Scalar::new(0, 0x100, 0, 0).leading_zeros(), 64 + 64 + 55);
+        assert_eq!(Scalar::new(0, 0x100, 0, 0).trailing_zeros(), 128 + 8);
+        assert_eq!((Scalar::ZERO - Scalar::ONE).leading_zeros(), 0);
+        assert_eq!(Scalar::MIN.trailing_zeros(), 319);
+    }
+
+    #[test]
+    fn it_iterates_over_bits() {
+        let one: Vec<bool> = Scalar::from_u64(1).bits().collect();
+
+        assert_eq!(one.len(), 256);
+        assert!(one[..255].iter().all(|b| !b));
+        assert!(one[255]);
+
+        let n: Vec<bool> = Scalar::from_u64(0b1011).non_zero_bits_msb().collect();
+        assert_eq!(n, vec![true, false, true, true]);
+
+        let msb = Scalar::new(0x8000000000000000, 0, 0, 0);
+        assert_eq!(msb.bits().next(), Some(true));
+        assert_eq!(msb.non_zero_bits_msb().count(), 256);
+        assert_eq!(Scalar::from_u64(0).non_zero_bits_msb().count(), 0);
+    }
+
+    #[test]
+    fn it_calculates_naf() {
+        // 7 = 8 - 1
+        assert_eq!(Scalar::from_u64(7).to_naf(), vec![-1, 0, 0, 1]);
+        assert!(Scalar::from_u64(0).to_naf().is_empty());
+
+        let ks = [
+            Scalar::from_u64(0xdeadbeef),
+            N - Scalar::from_u64(1),
+            Scalar::new(
+                0xb88b76b2b3bfffff,
+                0xffffffffffffffff,
+                0xffffffffffffffff,
+                0xffffffff4774868d,
+            ),
+        ];
+        for k in ks.iter() {
+            for w in 2..=8 {
+                let naf = k.to_wnaf(w);
+                let mut r = Scalar::from_u64(0);
+
+                for (i, digit) in naf.iter().enumerate().rev() {
+                    r = r + r;
+                    if *digit > 0 {
+                        r += Scalar::from_u64(*digit as u64);
+                    } else {
+                        r -= Scalar::from_u64(-*digit as u64);
+                    }
+                    if *digit != 0 {
+                        assert!(digit % 2 != 0);
+                        assert!(naf[(i + 1)..].iter().take(w as usize - 1).all(|d| *d == 0));
+                    }
+                }
+                assert_eq!(r, *k);
+            }
+        }
+    }
+
+    #[test]
+    fn it_tests_sign() {
+        let min_1 = Scalar::from_u64(0) - Scalar::from_u64(1);
+        let zero = Scalar::from_u64(0);
+        let one = Scalar::from_u64(1);
+
+        assert!(min_1.is_negative());
+        assert!(!min_1.is_positive());
+        assert!(!zero.is_negative());
+        assert!(!zero.is_positive());
+        assert!(!one.is_negative());
+        assert!(one.is_positive());
+        assert!(N.is_positive());
+        assert!((one - N).is_negative());
+    }
+
     #[test]
     fn it_tests_ordering() {
         let a = Scalar::from_u64(0) - Scalar::from_u64(0xffffffffffffffffu64); // 0 - 2^64 + 1
@@ -689,6 +1745,158 @@ mod tests {
         assert!(n_1 > min_1);
     }
 
+    #[test]
+    fn it_calculates_min_max_and_clamp() {
+        let min_1 = Scalar::ZERO - Scalar::ONE;
+        let a = Scalar::from_u64(0x2a);
+
+        assert_eq!(Scalar::min(min_1, a), min_1);
+        assert_eq!(Scalar::min(a, min_1), min_1);
+        assert_eq!(Scalar::max(min_1, a), a);
+        assert_eq!(Scalar::max(a, N), N);
+        assert_eq!(Scalar::min(Scalar::MIN, Scalar::MAX), Scalar::MIN);
+
+        assert_eq!(a.clamp(&Scalar::ZERO, &N), a);
+        assert_eq!(min_1.clamp(&Scalar::ZERO, &N), Scalar::ZERO);
+        assert_eq!(Scalar::MAX.clamp(&Scalar::ZERO, &N), N);
+        assert_eq!(N.clamp(&min_1, &a), a);
+        assert_eq!(a.clamp(&a, &a), a);
+    }
+
+    #[test]
+    fn it_multiplies_with_a_small_word() {
+        let a = Scalar::new(u64::MAX, 0x2a, u64::MAX, 0x7);
+
+        assert_eq!(a.mul_small(0), (Scalar::ZERO, 0));
+        assert_eq!(a.mul_small(1), (a, 0));
+        assert_eq!(a.mul_small(3), (a + a + a, 0));
+        assert_eq!(
+            a.mul_small(0x100).0,
+            Scalar::from([0x700, 0xffffffffffffff00, 0x2aff, 0xffffffffffffff00, 0xff])
+        );
+        assert_eq!(
+            Scalar::MAX.mul_small(2),
+            (Scalar::ZERO - Scalar::from_u64(2), 0)
+        );
+        assert_eq!(Scalar::MIN.mul_small(2), (Scalar::ZERO, 1));
+        assert_eq!(
+            (Scalar::ZERO - Scalar::ONE).mul_small(u32::MAX),
+            (
+                Scalar::ZERO - Scalar::from_u64(u32::MAX as u64),
+                u32::MAX as u64 - 1
+            )
+        );
+    }
+
+    #[test]
+    fn it_wraps_on_overflow() {
+        let a = Scalar::from_u64(0x2a);
+        let b = Scalar::new(u64::MAX, 0, u64::MAX, 0x7);
+
+        assert_eq!(Scalar::MAX.wrapping_add(&Scalar::ONE), Scalar::MIN);
+        assert_eq!(Scalar::MIN.wrapping_sub(&Scalar::ONE), Scalar::MAX);
+        assert_eq!(Scalar::MAX + Scalar::ONE, Scalar::MIN);
+        assert_eq!(Scalar::MIN - Scalar::ONE, Scalar::MAX);
+        assert_eq!(a.wrapping_add(&b), a + b);
+        assert_eq!(a.wrapping_sub(&b), a - b);
+        assert_eq!(a.wrapping_sub(&b).wrapping_add(&b), a);
+        assert!(Scalar::MAX > N && Scalar::MIN < Scalar::ZERO - N);
+    }
+
+    #[test]
+    fn it_applies_bitwise_operators() {
+        let a = Scalar::new(0xff00ff00ff00ff00, 0x0123456789abcdef, 0, u64::MAX);
+        let b = Scalar::ZERO - Scalar::from_u64(0x0f0f);
+        let c = N;
+
+        for (x, y) in [(a, b), (b, c), (a, c), (c, c)] {
+            assert_eq!((x & y) | (x ^ y), x | y);
+            assert_eq!(x ^ x, Scalar::ZERO);
+            assert_eq!(x & y, y & x);
+        }
+
+        // -1 is all ones
+        let mut r = a;
+        r &= Scalar::ZERO - Scalar::ONE;
+        assert_eq!(r, a);
+        r |= Scalar::ZERO - Scalar::ONE;
+        assert_eq!(r, Scalar::ZERO - Scalar::ONE);
+        r ^= &b;
+        assert_eq!(r, Scalar::from_u64(0x0f0e));
+    }
+
+    #[test]
+    fn it_propagates_borrows_through_all_limbs() {
+        // 0 - 1 borrows from every limb, giving -1 in two's complement
+        assert_eq!((Scalar::ZERO - Scalar::ONE).d, [u64::MAX; 5]);
+
+        // 2^256 - 1 borrows up to d[4] only
+        let two_256 = Scalar { d: [0, 0, 0, 0, 1] };
+        assert_eq!(
+            (two_256 - Scalar::ONE).d,
+            [u64::MAX, u64::MAX, u64::MAX, u64::MAX, 0]
+        );
+
+        // the borrow stops at the first limb that doesn't underflow
+        let a = Scalar { d: [0, 0, 5, 0, 0] };
+        assert_eq!((a - Scalar::ONE).d, [u64::MAX, u64::MAX, 4, 0, 0]);
+
+        // a borrow and a rhs limb of u64::MAX together
+        let b = Scalar { d: [0, u64::MAX, 0, 0, 0] };
+        assert_eq!(
+            (Scalar::ZERO - b - Scalar::ONE).d,
+            [u64::MAX, 0, u64::MAX, u64::MAX, u64::MAX]
+        );
+    }
+
+    #[test]
+    fn it_saturates_on_overflow() {
+        let a = Scalar::from_u64(0x2a);
+        let min_1 = Scalar::ZERO - Scalar::ONE;
+
+        assert_eq!(Scalar::MAX.saturating_add(&Scalar::ONE), Scalar::MAX);
+        assert_eq!(Scalar::MAX.saturating_add(&Scalar::MAX), Scalar::MAX);
+        assert_eq!(Scalar::MIN.saturating_add(&min_1), Scalar::MIN);
+        assert_eq!(Scalar::MIN.saturating_add(&Scalar::MIN), Scalar::MIN);
+        assert_eq!(Scalar::MIN.saturating_sub(&Scalar::ONE), Scalar::MIN);
+        assert_eq!(Scalar::MAX.saturating_sub(&min_1), Scalar::MAX);
+        assert_eq!(Scalar::ZERO.saturating_sub(&Scalar::MIN), Scalar::MAX);
+        assert_eq!(min_1.saturating_sub(&Scalar::MIN), Scalar::MAX);
+
+        assert_eq!(
+            Scalar::MAX.saturating_add(&min_1),
+            Scalar::MAX - Scalar::ONE
+        );
+        assert_eq!(
+            Scalar::MIN.saturating_sub(&min_1),
+            Scalar::MIN + Scalar::ONE
+        );
+        assert_eq!(a.saturating_add(&N), a + N);
+        assert_eq!(a.saturating_sub(&N), a - N);
+    }
+
+    #[test]
+    fn it_hashes_equal_scalars_equally() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |n: &Scalar| {
+            let mut h = DefaultHasher::new();
+            n.hash(&mut h);
+            h.finish()
+        };
+        let a = Scalar::ZERO - N;
+        let b = Scalar::from([SECP256K1_NI_0, SECP256K1_NI_1, 0x1, 0x0, u64::MAX]);
+
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(hash(&a), hash(&N));
+
+        let set: HashSet<Scalar> = [a, b, N, Scalar::ONE].into_iter().collect();
+        assert_eq!(set.len(), 3);
+    }
+
     #[test]
     fn it_modinv() {
         let mut a = Scalar::new(
@@ -741,6 +1949,84 @@ mod tests {
         assert_eq!(c, res3);
     }
 
+    #[test]
+    fn it_modinv_lehmer() {
+        let p = Scalar::new(
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xfffffffefffffc2f,
+        );
+        let mut a = Scalar::new(
+            0x7fffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffff7ffffe18,
+        );
+
+        a.modinv_lehmer(&p);
+        assert_eq!(a, Scalar::from_u64(0x2));
+
+        // compare with the binary gcd on pseudo random inputs
+        let mut seed = Scalar::from_u64(0x2a);
+        for _ in 0..200 {
+            seed = seed.mulmod(&seed) + Scalar::from_u64(1);
+            for m in [p, N] {
+                let mut x = seed;
+                x.normalize(&m);
+                let mut y = x;
+
+                x.modinv_inner_with(&m, ModInvStrategy::Lehmer);
+                y.modinv_inner_with(&m, ModInvStrategy::BinaryGcd);
+                assert_eq!(x, y);
+            }
+        }
+
+        let mut small = Scalar::from_u64(3);
+        small.modinv_lehmer(&N);
+        assert_eq!(small.mulmod(&Scalar::from_u64(3)), Scalar::from_u64(1));
+    }
+
+    #[test]
+    fn it_modinv_extended_euclidean() {
+        let p = Scalar::new(
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xfffffffefffffc2f,
+        );
+        let mut a = Scalar::new(
+            0x7fffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffff7ffffe18,
+        );
+
+        a.modinv_extended_euclidean(&p);
+        assert_eq!(a, Scalar::from_u64(0x2));
+
+        // compare with the binary gcd on pseudo random inputs
+        let mut seed = Scalar::from_u64(0x2a);
+        for _ in 0..200 {
+            seed = seed.mulmod(&seed) + Scalar::from_u64(1);
+            for m in [p, N] {
+                let mut x = seed;
+                x.normalize(&m);
+                let mut y = x;
+
+                x.modinv_inner_with(&m, ModInvStrategy::ExtendedEuclidean);
+                y.modinv_inner_with(&m, ModInvStrategy::BinaryGcd);
+                assert_eq!(x, y);
+            }
+        }
+
+        for n in [1, 3, u64::MAX] {
+            let mut small = Scalar::from_u64(n);
+            small.modinv_extended_euclidean(&N);
+            assert_eq!(small.mulmod(&Scalar::from_u64(n)), Scalar::from_u64(1));
+        }
+    }
+
     #[test]
     fn it_multiply_scalars() {
         let mut n1 = Scalar::new(
@@ -761,6 +2047,30 @@ mod tests {
         assert_eq!(n1, res);
     }
 
+    #[test]
+    fn it_mul_adds_scalars() {
+        let two = Scalar::from_u64(2);
+
+        assert_eq!(
+            two.mul_add(&Scalar::from_u64(3), &Scalar::from_u64(1)),
+            Scalar::from_u64(7)
+        );
+
+        // (N - 1) * (N - 1) + (N - 1) = 0 % N
+        let n_1 = N - Scalar::from_u64(1);
+        assert!(n_1.mul_add(&n_1, &n_1).is_zero());
+
+        let a = Scalar::new(
+            0xb88b76b2b3bfffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffff4774868d,
+        );
+        let mut expected = a.mulmod(&a) + n_1;
+        expected.normalize(&N);
+        assert_eq!(a.mul_add(&a, &n_1), expected);
+    }
+
     #[test]
     fn it_multiply_scalars2() {
         let mut n1 = N - Scalar::from_u64(0x42);
@@ -0,0 +1,351 @@
+use std::convert::TryInto;
+use std::fmt;
+
+use crate::ecc::{Pt, G};
+use crate::error::Error;
+use crate::field::El;
+use crate::hmac::tagged_hash;
+use crate::key::{PrivateKey, PublicKey, XOnlyPublicKey};
+use crate::rng::{random_scalar, SecureRandom};
+use crate::scalar::{negate_mod_n, Scalar};
+
+/// Reduce 32 bytes big endian % N
+fn scalar_mod_n(b: &[u8; 32]) -> Scalar {
+    let mut n = Scalar::from_bytes(b);
+
+    n.reduce(n.get_overflow());
+    n
+}
+
+/// Calculate the BIP-340 challenge e = hashBIP0340/challenge(r || P || msg) % N
+fn challenge(rx: &[u8; 32], pk: &XOnlyPublicKey, msg: &[u8]) -> Scalar {
+    let mut buf = Vec::with_capacity(64 + msg.len());
+
+    buf.extend_from_slice(rx);
+    buf.extend_from_slice(&pk.serialize());
+    buf.extend_from_slice(msg);
+
+    scalar_mod_n(&tagged_hash(b"BIP0340/challenge", &buf))
+}
+
+/// Calculate the BIP-340 challenge of the nonce point r, the key p and msg
+///
+/// This is e = hashBIP0340/challenge(r || p || msg) % N, the hash bound by
+/// s = k + e * d, exposed for protocols building Schnorr signatures
+/// themselves such as MuSig2 or FROST
+pub fn schnorr_challenge(r: &XOnlyPublicKey, p: &XOnlyPublicKey, msg: &[u8]) -> Scalar {
+    challenge(&r.serialize(), p, msg)
+}
+
+/// Nonce of a BIP-340 signature, the secret k and the point R = k * G
+///
+/// Both are computed together so they can't go out of sync, and k is negated
+/// when needed so that R has an even y. A nonce is consumed by the signature
+/// because signing two messages with the same nonce reveals the private key
+pub struct SchnorrNonce {
+    k: Scalar,
+    r: Pt,
+}
+
+impl SchnorrNonce {
+    /// Create a nonce from k0 in [1, N)
+    fn from_scalar(k0: &Scalar) -> Self {
+        debug_assert!(!k0.is_zero());
+
+        let mut r = G * k0;
+        r.y.reduce();
+
+        if r.y.is_even() {
+            Self { k: *k0, r }
+        } else {
+            Self { k: negate_mod_n(k0), r: -r }
+        }
+    }
+
+    /// Draw a fresh nonce from a random number generator
+    pub fn generate(rng: &mut impl SecureRandom) -> Self {
+        Self::from_scalar(&random_scalar(rng))
+    }
+
+    /// Public point R of the nonce, its y is even
+    pub fn point(&self) -> &Pt {
+        &self.r
+    }
+}
+
+impl fmt::Debug for SchnorrNonce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SchnorrNonce")
+            .field("k", &"<redacted>")
+            .field("r", &self.r)
+            .finish()
+    }
+}
+
+/// Create a BIP-340 signature of msg
+///
+/// aux_rand is mixed into the nonce, it should be fresh randomness but the
+/// signature is still secure with a constant value
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let secret = Scalar::from_bytes(&hash256("the force".as_bytes()));
+/// let pk = PrivateKey::new(secret);
+/// let sig = schnorr_sign(&pk, "May the force be with you".as_bytes(), &[0u8; 32]);
+/// ```
+pub fn schnorr_sign(key: &PrivateKey, msg: &[u8], aux_rand: &[u8; 32]) -> [u8; 64] {
+    let secret = key.secret();
+    let pk = XOnlyPublicKey::from_public_key(&PublicKey::from_secret(secret));
+    let d = even_y_secret(secret);
+
+    let mut t = d.to_bytes();
+    for (b, a) in t
+        .iter_mut()
+        .zip(tagged_hash(b"BIP0340/aux", aux_rand).iter())
+    {
+        *b ^= a;
+    }
+
+    let mut buf = Vec::with_capacity(64 + msg.len());
+    buf.extend_from_slice(&t);
+    buf.extend_from_slice(&pk.serialize());
+    buf.extend_from_slice(msg);
+    let k0 = scalar_mod_n(&tagged_hash(b"BIP0340/nonce", &buf));
+
+    sign(&d, &pk, msg, SchnorrNonce::from_scalar(&k0))
+}
+
+/// Create a BIP-340 signature of msg with a nonce chosen by the caller
+///
+/// The nonce must never be reused, prefer schnorr_sign unless the nonce has
+/// to be known before signing
+pub fn schnorr_sign_with_nonce(key: &PrivateKey, msg: &[u8], nonce: SchnorrNonce) -> [u8; 64] {
+    let secret = key.secret();
+    let pk = XOnlyPublicKey::from_public_key(&PublicKey::from_secret(secret));
+
+    sign(&even_y_secret(secret), &pk, msg, nonce)
+}
+
+/// Calculate s = k + e * d with d the even y secret of the x-only key pk
+fn sign(d: &Scalar, pk: &XOnlyPublicKey, msg: &[u8], nonce: SchnorrNonce) -> [u8; 64] {
+    let rx = nonce.r.x.to_bytes();
+
+    let e = challenge(&rx, pk, msg);
+    let s = e.mul_add(d, &nonce.k);
+
+    let mut sig = [0u8; 64];
+    sig[0..32].copy_from_slice(&rx);
+    sig[32..64].copy_from_slice(&s.to_bytes());
+    sig
+}
+
+/// Negate the secret if needed so that its public key has an even y
+fn even_y_secret(secret: &Scalar) -> Scalar {
+    let mut p = G * secret;
+
+    p.y.reduce();
+    if p.y.is_even() {
+        *secret
+    } else {
+        negate_mod_n(secret)
+    }
+}
+
+/// Verify a BIP-340 signature of msg
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let secret = Scalar::from_bytes(&hash256("the force".as_bytes()));
+/// let pk = PrivateKey::new(secret);
+/// let xpk = XOnlyPublicKey::from_public_key(&PublicKey::from_secret(&secret));
+/// let msg = "May the force be with you".as_bytes();
+/// let sig = schnorr_sign(&pk, msg, &[0u8; 32]);
+///
+/// assert!(schnorr_verify(&xpk, msg, &sig));
+/// ```
+#[must_use]
+pub fn schnorr_verify(pk: &XOnlyPublicKey, msg: &[u8], sig: &[u8; 64]) -> bool {
+    schnorr_verify_returning_nonce(pk, msg, sig).is_ok()
+}
+
+/// Verify a BIP-340 signature of msg and return its nonce point R
+///
+/// R = s * G - e * P is computed during the verification anyway, protocols
+/// such as FROST need it to check partial signatures. Fails with
+/// `InvalidSignature` whenever `schnorr_verify` returns false
+pub fn schnorr_verify_returning_nonce(
+    pk: &XOnlyPublicKey,
+    msg: &[u8],
+    sig: &[u8; 64],
+) -> Result<XOnlyPublicKey, Error> {
+    let rx: [u8; 32] = sig[0..32].try_into().unwrap();
+    let sb: [u8; 32] = sig[32..64].try_into().unwrap();
+
+    // r < P, s < N
+    if El::from_bytes(&rx).to_bytes() != rx {
+        return Err(Error::InvalidSignature);
+    }
+    let s = Scalar::from_bytes(&sb);
+    if s.get_overflow() != 0 {
+        return Err(Error::InvalidSignature);
+    }
+
+    // R = s * G - e * P
+    let e = challenge(&rx, pk, msg);
+    let mut r = G.mul_vartime(&s) + pk.key.mul_vartime(&negate_mod_n(&e));
+    if r.inf {
+        return Err(Error::InvalidSignature);
+    }
+    r.x.reduce();
+    r.y.reduce();
+
+    if !r.y.is_even() || r.x.to_bytes() != rx {
+        return Err(Error::InvalidSignature);
+    }
+    Ok(XOnlyPublicKey { key: r })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use crate::scalar::N;
+
+    fn from_hex32(s: &str) -> [u8; 32] {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    fn from_hex64(s: &str) -> [u8; 64] {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    // BIP-340 test vectors 0 and 1
+    const VECTORS: [(&str, &str, &str, &str, &str); 2] = [
+        (
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+             25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+        ),
+        (
+            "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+             8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+        ),
+    ];
+
+    #[test]
+    fn it_signs_bip340_vectors() {
+        for (secret, pk, aux, msg, sig) in VECTORS.iter() {
+            let key = PrivateKey::new(Scalar::from_bytes(&from_hex32(secret)));
+            let pk = XOnlyPublicKey::parse(&from_hex32(pk)).unwrap();
+            let msg = from_hex32(msg);
+            let res = schnorr_sign(&key, &msg, &from_hex32(aux));
+
+            assert_eq!(res, from_hex64(sig));
+            assert!(schnorr_verify(&pk, &msg, &res));
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_schnorr_signatures() {
+        let (_, pk, _, msg, sig) = VECTORS[1];
+        let pk = XOnlyPublicKey::parse(&from_hex32(pk)).unwrap();
+        let msg = from_hex32(msg);
+        let sig = from_hex64(sig);
+
+        // wrong message
+        assert!(!schnorr_verify(&pk, &[0u8; 32], &sig));
+
+        // tampered s
+        let mut bad = sig;
+        bad[63] ^= 0x01;
+        assert!(!schnorr_verify(&pk, &msg, &bad));
+
+        // r >= P
+        let mut bad = sig;
+        bad[0..32].copy_from_slice(&[0xff; 32]);
+        assert!(!schnorr_verify(&pk, &msg, &bad));
+
+        // s >= N
+        let mut bad = sig;
+        bad[32..64].copy_from_slice(&N.to_bytes());
+        assert!(!schnorr_verify(&pk, &msg, &bad));
+    }
+
+    #[test]
+    fn it_exposes_the_challenge() {
+        let (_, pk, _, msg, sig) = VECTORS[1];
+        let pk = XOnlyPublicKey::parse(&from_hex32(pk)).unwrap();
+        let msg = from_hex32(msg);
+        let sig = from_hex64(sig);
+        let r = XOnlyPublicKey::parse(&sig[0..32].try_into().unwrap()).unwrap();
+        let s = Scalar::from_bytes(&sig[32..64].try_into().unwrap());
+
+        // s * G = R + e * P
+        let e = schnorr_challenge(&r, &pk, &msg);
+        assert_eq!(G * s, r.key + pk.key * e);
+    }
+
+    #[test]
+    fn it_returns_the_nonce_point() {
+        for (_, pk, _, msg, sig) in VECTORS.iter() {
+            let pk = XOnlyPublicKey::parse(&from_hex32(pk)).unwrap();
+            let msg = from_hex32(msg);
+            let sig = from_hex64(sig);
+            let r = XOnlyPublicKey::parse(&sig[0..32].try_into().unwrap()).unwrap();
+
+            assert_eq!(schnorr_verify_returning_nonce(&pk, &msg, &sig), Ok(r));
+
+            let mut bad = sig;
+            bad[63] ^= 0x01;
+            assert_eq!(
+                schnorr_verify_returning_nonce(&pk, &msg, &bad),
+                Err(Error::InvalidSignature)
+            );
+        }
+    }
+
+    struct TestRng(u8);
+
+    impl SecureRandom for TestRng {
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.0 += 1;
+            dest.fill(self.0);
+        }
+    }
+
+    #[test]
+    fn it_signs_with_an_explicit_nonce() {
+        let key = PrivateKey::new(Scalar::from_bytes(&from_hex32(VECTORS[1].0)));
+        let pk = XOnlyPublicKey::parse(&from_hex32(VECTORS[1].1)).unwrap();
+        let msg = from_hex32(VECTORS[1].3);
+        let mut rng = TestRng(0);
+
+        let nonce = SchnorrNonce::generate(&mut rng);
+        let mut r = G * nonce.k;
+        r.y.reduce();
+        assert_eq!(r, *nonce.point());
+        assert!(nonce.point().y.is_even());
+
+        let rx = nonce.point().x.to_bytes();
+        let sig = schnorr_sign_with_nonce(&key, &msg, nonce);
+        assert_eq!(sig[0..32], rx);
+        assert!(schnorr_verify(&pk, &msg, &sig));
+
+        let other = schnorr_sign_with_nonce(&key, &msg, SchnorrNonce::generate(&mut rng));
+        assert_ne!(sig, other);
+        assert!(schnorr_verify(&pk, &msg, &other));
+    }
+}
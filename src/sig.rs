@@ -1,5 +1,12 @@
+use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ecc::G;
 use crate::error::Error;
-use crate::scalar::Scalar;
+use crate::hex;
+use crate::key::PublicKey;
+use crate::scalar::{negate_mod_n, Scalar};
 
 /// ECDSA signature
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -22,7 +29,7 @@ impl Signature {
                 i += 1;
             }
             let len = 32 - i;
-            if nbin[i] != 0 {
+            if nbin[i] >= 0x80 {
                 res[0] = 0;
                 res[1..(1 + len)].copy_from_slice(&nbin[i..32]);
                 len + 1
@@ -45,9 +52,39 @@ impl Signature {
         (res, len + 2)
     }
 
+    /// Serialize a signature in the compact format: r and s in big endian
+    pub fn serialize_compact(&self) -> [u8; 64] {
+        let mut res = [0u8; 64];
+
+        res[0..32].copy_from_slice(&self.r.to_bytes());
+        res[32..64].copy_from_slice(&self.s.to_bytes());
+
+        res
+    }
+
+    /// Parse a signature in the compact format
+    pub fn parse_compact(bin: &[u8; 64]) -> Signature {
+        let r = Scalar::from_bytes(bin[0..32].try_into().unwrap());
+        let s = Scalar::from_bytes(bin[32..64].try_into().unwrap());
+
+        Signature { r, s }
+    }
+
+    /// Parse a DER encoded signature
+    ///
+    /// The encoding must be strict: integers are positive and use the minimal
+    /// number of bytes
     pub fn parse_der(bin: &[u8]) -> Result<Self, Error> {
         fn parse_scalar(bin: &[u8], len: usize) -> Result<Scalar, Error> {
             let mut b = [0u8; 32];
+            // negative or empty integer
+            if len == 0 || bin[0] >= 0x80 {
+                return Err(Error::InvalidBuffer);
+            }
+            // a leading 0 is only allowed before a byte with the first bit set
+            if len > 1 && bin[0] == 0x0 && bin[1] < 0x80 {
+                return Err(Error::InvalidBuffer);
+            }
             let start = if bin[0] == 0x0 { 1 } else { 0 };
             if len > (32 + start) {
                 return Err(Error::InvalidBuffer);
@@ -79,6 +116,265 @@ impl Signature {
 
         Ok(Signature { r, s })
     }
+
+    /// Parse a DER encoded signature with the rules of Bitcoin Core's
+    /// `ecdsa_signature_parse_der_lax`, for signatures found in old transactions
+    ///
+    /// Compared to [`parse_der`](Self::parse_der):
+    /// - the sequence length is not checked, and long form lengths are skipped
+    /// - integer lengths can use the long form, with any number of leading zeros
+    /// - integers can have any number of leading zeros, and their first bit is
+    ///   not checked, so "negative" values are read as positive
+    /// - any bytes after s are ignored
+    /// - r or s longer than 32 bytes or not lower than N give r = s = 0,
+    ///   a signature that never verifies, instead of an error
+    ///
+    /// The tags and the integer lengths must still fit in the buffer. As with
+    /// `parse_der`, high s values are accepted.
+    pub fn parse_der_lax(bin: &[u8]) -> Result<Self, Error> {
+        // read a length byte, with the long form if its first bit is set
+        fn parse_len(bin: &[u8], pos: &mut usize) -> Result<usize, Error> {
+            let mut lenbyte = *bin.get(*pos).ok_or(Error::InvalidBuffer)? as usize;
+            *pos += 1;
+            if lenbyte & 0x80 == 0 {
+                return Ok(lenbyte);
+            }
+            lenbyte -= 0x80;
+            if lenbyte > bin.len() - *pos {
+                return Err(Error::InvalidBuffer);
+            }
+            while lenbyte > 0 && bin[*pos] == 0 {
+                *pos += 1;
+                lenbyte -= 1;
+            }
+            if lenbyte >= std::mem::size_of::<usize>() {
+                return Err(Error::InvalidBuffer);
+            }
+            let mut len = 0;
+            while lenbyte > 0 {
+                len = (len << 8) + bin[*pos] as usize;
+                *pos += 1;
+                lenbyte -= 1;
+            }
+            Ok(len)
+        }
+        // read an integer tag and length, return the integer bytes
+        fn parse_int<'a>(bin: &'a [u8], pos: &mut usize) -> Result<&'a [u8], Error> {
+            if bin.get(*pos) != Some(&0x02) {
+                return Err(Error::InvalidBuffer);
+            }
+            *pos += 1;
+            let len = parse_len(bin, pos)?;
+            if len > bin.len() - *pos {
+                return Err(Error::InvalidBuffer);
+            }
+            let start = *pos;
+            *pos += len;
+
+            Ok(&bin[start..*pos])
+        }
+        // strip the leading zeros, None if the value is too large
+        fn to_scalar(mut int: &[u8]) -> Option<Scalar> {
+            while let Some((0, rest)) = int.split_first() {
+                int = rest;
+            }
+            if int.len() > 32 {
+                return None;
+            }
+            let mut b = [0u8; 32];
+            b[(32 - int.len())..].copy_from_slice(int);
+            let n = Scalar::from_bytes(&b);
+
+            if n.get_overflow() != 0 {
+                return None;
+            }
+            Some(n)
+        }
+
+        // sequence tag and length
+        if bin.first() != Some(&0x30) {
+            return Err(Error::InvalidBuffer);
+        }
+        let mut pos = 1;
+        let lenbyte = *bin.get(pos).ok_or(Error::InvalidBuffer)? as usize;
+        pos += 1;
+        if lenbyte & 0x80 != 0 {
+            if lenbyte - 0x80 > bin.len() - pos {
+                return Err(Error::InvalidBuffer);
+            }
+            pos += lenbyte - 0x80;
+        }
+
+        let r = parse_int(bin, &mut pos)?;
+        let s = parse_int(bin, &mut pos)?;
+
+        match (to_scalar(r), to_scalar(s)) {
+            (Some(r), Some(s)) => Ok(Signature { r, s }),
+            _ => Ok(Signature { r: Scalar::ZERO, s: Scalar::ZERO }),
+        }
+    }
+
+    /// Recover the public keys that can have created the signature of a hash
+    ///
+    /// Without a recovery id, R can be either of the 2 points with r as x
+    /// coordinate, the keys are returned for R with an even y then an odd y.
+    /// The unlikely case where R.x = r + N is not covered
+    pub fn recover_public_keys(&self, hash: &[u8; 32]) -> [Option<PublicKey>; 2] {
+        // r and s must be in [1, N - 1]
+        if self.r.is_zero() || self.r.get_overflow() != 0 {
+            return [None, None];
+        }
+        if self.s.is_zero() || self.s.get_overflow() != 0 {
+            return [None, None];
+        }
+
+        let mut r_inv = self.r;
+        r_inv.modinv_inner();
+
+        // Q = r^-1 * (s * R - z * G)
+        let z = Scalar::from_bytes(hash);
+        let u1 = negate_mod_n(&z.mulmod(&r_inv));
+        let u2 = self.s.mulmod(&r_inv);
+        let recover = |prefix: u8| {
+            let mut sec = [prefix; 33];
+
+            sec[1..33].copy_from_slice(&self.r.to_bytes());
+            let big_r = PublicKey::parse_sec(&sec).ok()?;
+            let q = G.mul_vartime(&u1) + big_r.key.mul_vartime(&u2);
+
+            if q.inf {
+                None
+            } else {
+                Some(PublicKey { key: q })
+            }
+        };
+
+        [recover(0x02), recover(0x03)]
+    }
+
+    /// Verify a DER encoded signature of a hash against a SEC1 encoded key
+    ///
+    /// This parses both with `Signature::parse_der` and `PublicKey::parse_sec`
+    /// and returns their error if either is invalid, then `InvalidSignature`
+    /// if the verification fails
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use estel_secp256k1::*;
+    ///
+    /// let secret = Scalar::from_bytes(&hash256("the force".as_bytes()));
+    /// let mut pk = PublicKey::from_secret(&secret);
+    /// let hash = hash256("The greatest teacher failure is".as_bytes());
+    /// let sig = PrivateKey::new(secret).sign(&Scalar::from_bytes(&hash));
+    /// let (der, len) = sig.serialize_der();
+    /// let sec = pk.serialize_sec_compressed();
+    ///
+    /// assert!(Signature::verify_bytes(&der[..len], &hash, &sec).is_ok());
+    /// ```
+    pub fn verify_bytes(
+        sig_der: &[u8],
+        msg_hash: &[u8; 32],
+        pubkey_bytes: &[u8],
+    ) -> Result<(), Error> {
+        let sig = Signature::parse_der(sig_der)?;
+        let pk = PublicKey::parse_sec(pubkey_bytes)?;
+
+        if pk.verify(&Scalar::from_bytes(msg_hash), &sig) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+/// ECDSA signature with the recovery id of its public key
+///
+/// Bit 0 of recid is the parity of R.y and bit 1 is set when R.x is r + N
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecoverableSignature {
+    pub sig: Signature,
+    pub recid: u8,
+}
+
+impl RecoverableSignature {
+    /// Serialize in the 65 bytes compact format: recid, r and s in big endian
+    pub fn to_compact(&self) -> [u8; 65] {
+        let mut res = [0u8; 65];
+
+        res[0] = self.recid;
+        res[1..65].copy_from_slice(&self.sig.serialize_compact());
+
+        res
+    }
+
+    /// Parse the 65 bytes compact format, recid must be in [0, 3]
+    pub fn from_compact(bin: &[u8; 65]) -> Result<Self, Error> {
+        if bin[0] > 3 {
+            return Err(Error::InvalidBuffer);
+        }
+        let sig = Signature::parse_compact(bin[1..65].try_into().unwrap());
+
+        Ok(RecoverableSignature { sig, recid: bin[0] })
+    }
+}
+
+/// ECDSA signature of a Bitcoin transaction input with its sighash type
+///
+/// The sighash type is one of SIGHASH_ALL (0x01), SIGHASH_NONE (0x02) or
+/// SIGHASH_SINGLE (0x03), optionally with SIGHASH_ANYONECANPAY (0x80)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BitcoinSignature {
+    pub sig: Signature,
+    pub sighash_type: u8,
+}
+
+impl BitcoinSignature {
+    /// Serialize the signature as DER followed by the sighash type byte
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (der, len) = self.sig.serialize_der();
+        let mut res = Vec::with_capacity(len + 1);
+
+        res.extend_from_slice(&der[..len]);
+        res.push(self.sighash_type);
+
+        res
+    }
+
+    /// Parse a strict DER signature followed by a known sighash type byte
+    pub fn from_bytes(bin: &[u8]) -> Result<Self, Error> {
+        let (sighash_type, der) = bin.split_last().ok_or(Error::InvalidBuffer)?;
+
+        if !matches!(sighash_type & !0x80, 0x01..=0x03) {
+            return Err(Error::InvalidBuffer);
+        }
+        let sig = Signature::parse_der(der)?;
+
+        Ok(BitcoinSignature { sig, sighash_type: *sighash_type })
+    }
+}
+
+/// Format the signature as 128 hexadecimal characters (compact format)
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        hex::write(f, &self.serialize_compact())
+    }
+}
+
+/// Parse a signature from hexadecimal
+///
+/// 128 characters are parsed as the compact format, anything else as DER
+impl FromStr for Signature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let bin = hex::decode(s)?;
+
+        match bin.as_slice().try_into() {
+            Ok(compact) => Ok(Signature::parse_compact(compact)),
+            Err(_) => Signature::parse_der(&bin),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -165,5 +461,233 @@ mod tests {
         let bin = [0u8; 72];
         let exp = Signature::parse_der(&bin[0..72]).unwrap_err();
         assert_eq!(exp, Error::InvalidBuffer);
+
+        // r = 0x00 0x01 is not minimal, r = 0x81 is negative, r is empty
+        let bin = [0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01];
+        let exp = Signature::parse_der(&bin).unwrap_err();
+        assert_eq!(exp, Error::InvalidBuffer);
+
+        let bin = [0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01];
+        let exp = Signature::parse_der(&bin).unwrap_err();
+        assert_eq!(exp, Error::InvalidBuffer);
+
+        let bin = [0x30, 0x05, 0x02, 0x00, 0x02, 0x01, 0x01];
+        let exp = Signature::parse_der(&bin).unwrap_err();
+        assert_eq!(exp, Error::InvalidBuffer);
+    }
+
+    #[test]
+    fn it_parses_lax_der_signatures() {
+        let sig = Signature { r: Scalar::from_u64(0x7f), s: Scalar::from_u64(0x81) };
+        let strict = [0x30, 0x07, 0x02, 0x01, 0x7f, 0x02, 0x02, 0x00, 0x81];
+        assert_eq!(Signature::parse_der_lax(&strict).unwrap(), sig);
+
+        let lax: [&[u8]; 5] = [
+            // wrong sequence length
+            &[0x30, 0x42, 0x02, 0x01, 0x7f, 0x02, 0x02, 0x00, 0x81],
+            // long form sequence and integer lengths
+            &[
+                0x30, 0x81, 0x09, 0x02, 0x82, 0x00, 0x01, 0x7f, 0x02, 0x81, 0x02, 0x00, 0x81,
+            ],
+            // redundant leading zeros in r
+            &[
+                0x30, 0x09, 0x02, 0x03, 0x00, 0x00, 0x7f, 0x02, 0x02, 0x00, 0x81,
+            ],
+            // s is "negative"
+            &[0x30, 0x06, 0x02, 0x01, 0x7f, 0x02, 0x01, 0x81],
+            // trailing bytes
+            &[
+                0x30, 0x07, 0x02, 0x01, 0x7f, 0x02, 0x02, 0x00, 0x81, 0x01, 0x01,
+            ],
+        ];
+        for bin in lax.iter() {
+            assert_eq!(Signature::parse_der(bin).unwrap_err(), Error::InvalidBuffer);
+            assert_eq!(Signature::parse_der_lax(bin).unwrap(), sig);
+        }
+
+        // r >= N is parsed as the zero signature
+        let mut bin = vec![0x30, 0x26, 0x02, 0x21, 0x00];
+        bin.extend_from_slice(&Scalar::GROUP_ORDER.to_bytes());
+        bin.extend_from_slice(&[0x02, 0x01, 0x01]);
+        let zero = Signature { r: Scalar::ZERO, s: Scalar::ZERO };
+        assert_eq!(Signature::parse_der_lax(&bin).unwrap(), zero);
+
+        let invalid: [&[u8]; 5] = [
+            &[],
+            &[0x31, 0x06, 0x02, 0x01, 0x7f, 0x02, 0x01, 0x01],
+            &[0x30, 0x06, 0x03, 0x01, 0x7f, 0x02, 0x01, 0x01],
+            &[0x30, 0x06, 0x02, 0x01, 0x7f, 0x02, 0x02, 0x01],
+            &[
+                0x30, 0x06, 0x02, 0x89, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+            ],
+        ];
+        for bin in invalid.iter() {
+            assert_eq!(
+                Signature::parse_der_lax(bin).unwrap_err(),
+                Error::InvalidBuffer
+            );
+        }
+    }
+
+    #[test]
+    fn it_serializes_small_signature() {
+        let sig = Signature { r: Scalar::from_u64(0x7f), s: Scalar::from_u64(0x80) };
+
+        let (bin, len) = sig.serialize_der();
+        let exp = [0x30, 0x07, 0x02, 0x01, 0x7f, 0x02, 0x02, 0x00, 0x80];
+
+        assert_eq!(bin[..len], exp);
+        assert_eq!(Signature::parse_der(&bin[..len]).unwrap(), sig);
+    }
+
+    #[test]
+    fn it_recovers_public_keys() {
+        use crate::hmac::hash256;
+        use crate::key::PrivateKey;
+
+        for i in 1..10u8 {
+            let secret = Scalar::from_bytes(&hash256(&[i]));
+            let pk = PublicKey::from_secret(&secret);
+            let hash = hash256(b"The greatest teacher failure is");
+            let sig = PrivateKey::new(secret).sign(&Scalar::from_bytes(&hash));
+            let keys = sig.recover_public_keys(&hash);
+
+            assert!(keys.contains(&Some(pk)));
+            assert_ne!(keys[0], keys[1]);
+            for key in keys.iter().flatten() {
+                assert!(key.verify(&Scalar::from_bytes(&hash), &sig));
+            }
+        }
+
+        let sig = Signature { r: Scalar::ZERO, s: Scalar::ONE };
+        assert_eq!(sig.recover_public_keys(&[0x2a; 32]), [None, None]);
+    }
+
+    #[test]
+    fn it_verifies_signatures_from_bytes() {
+        use crate::hmac::hash256;
+        use crate::key::PrivateKey;
+
+        let secret = Scalar::from_bytes(&hash256(b"the force"));
+        let mut pk = PublicKey::from_secret(&secret);
+        let hash = hash256(b"The greatest teacher failure is");
+        let sig = PrivateKey::new(secret).sign(&Scalar::from_bytes(&hash));
+        let (der, len) = sig.serialize_der();
+        let der = &der[..len];
+
+        assert_eq!(
+            Signature::verify_bytes(der, &hash, &pk.serialize_sec_compressed()),
+            Ok(())
+        );
+        assert_eq!(
+            Signature::verify_bytes(der, &hash, &pk.serialize_sec_uncompressed()),
+            Ok(())
+        );
+        assert_eq!(
+            Signature::verify_bytes(der, &[0u8; 32], &pk.serialize_sec_compressed()),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            Signature::verify_bytes(&der[1..], &hash, &pk.serialize_sec_compressed()),
+            Signature::parse_der(&der[1..]).map(|_| ())
+        );
+        assert_eq!(
+            Signature::verify_bytes(der, &hash, &[0x02; 32]),
+            Err(Error::InvalidBuffer)
+        );
+    }
+
+    #[test]
+    fn it_serializes_recoverable_signature() {
+        let sig = Signature { r: Scalar::from_u64(0x7f), s: Scalar::from_u64(0x80) };
+
+        for recid in 0..4 {
+            let rsig = RecoverableSignature { sig, recid };
+            let bin = rsig.to_compact();
+
+            assert_eq!(bin[0], recid);
+            assert_eq!(bin[32], 0x7f);
+            assert_eq!(bin[64], 0x80);
+            assert_eq!(RecoverableSignature::from_compact(&bin).unwrap(), rsig);
+        }
+
+        let mut bin = RecoverableSignature { sig, recid: 0 }.to_compact();
+        bin[0] = 4;
+        let exp = RecoverableSignature::from_compact(&bin).unwrap_err();
+        assert_eq!(exp, Error::InvalidBuffer);
+    }
+
+    #[test]
+    fn it_serializes_bitcoin_signature() {
+        let sig = Signature { r: Scalar::from_u64(0x7f), s: Scalar::from_u64(0x80) };
+
+        for sighash_type in [0x01, 0x02, 0x03, 0x81, 0x82, 0x83] {
+            let btc_sig = BitcoinSignature { sig, sighash_type };
+            let bin = btc_sig.to_bytes();
+
+            assert_eq!(
+                bin[..9],
+                [0x30, 0x07, 0x02, 0x01, 0x7f, 0x02, 0x02, 0x00, 0x80]
+            );
+            assert_eq!(bin[9], sighash_type);
+            assert_eq!(BitcoinSignature::from_bytes(&bin).unwrap(), btc_sig);
+        }
+
+        for sighash_type in [0x00, 0x04, 0x80, 0x84, 0xff] {
+            let bin = BitcoinSignature { sig, sighash_type }.to_bytes();
+            let exp = BitcoinSignature::from_bytes(&bin).unwrap_err();
+            assert_eq!(exp, Error::InvalidBuffer);
+        }
+
+        let bin = [0x30, 0x07, 0x02, 0x01, 0x7f, 0x02, 0x02, 0x00, 0x80];
+        let exp = BitcoinSignature::from_bytes(&bin).unwrap_err();
+        assert_eq!(exp, Error::InvalidBuffer);
+        assert_eq!(
+            BitcoinSignature::from_bytes(&[]).unwrap_err(),
+            Error::InvalidBuffer
+        );
+    }
+
+    #[test]
+    fn it_formats_and_parses_hex_signature() {
+        let sig = Signature {
+            r: Scalar::new(
+                0xe45a150a8eafef6f,
+                0x5a3dfef6d3728674,
+                0x92eb9d31e3ffb254,
+                0x013767c71e093276,
+            ),
+            s: Scalar::from_u64(0x80),
+        };
+
+        let compact = sig.to_string();
+        assert_eq!(
+            compact,
+            "e45a150a8eafef6f5a3dfef6d372867492eb9d31e3ffb254013767c71e093276\
+             0000000000000000000000000000000000000000000000000000000000000080"
+        );
+        assert_eq!(compact.parse::<Signature>().unwrap(), sig);
+        assert_eq!(compact.to_uppercase().parse::<Signature>().unwrap(), sig);
+
+        let (der, len) = sig.serialize_der();
+        let der: String = der[..len].iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(der.parse::<Signature>().unwrap(), sig);
+    }
+
+    #[test]
+    fn it_cannot_parse_hex_signature() {
+        assert_eq!("".parse::<Signature>().unwrap_err(), Error::InvalidBuffer);
+        assert_eq!(
+            "3006020".parse::<Signature>().unwrap_err(),
+            Error::InvalidHex
+        );
+        assert_eq!(
+            "30060201010201zz".parse::<Signature>().unwrap_err(),
+            Error::InvalidHex
+        );
+        assert_eq!(
+            "3006020101020101ff".parse::<Signature>().unwrap_err(),
+            Error::InvalidBuffer
+        );
     }
 }
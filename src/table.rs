@@ -0,0 +1,213 @@
+use crate::ecc::{Pt, G};
+use crate::jacobian::{JacobianPt, JACOBIAN_INFINITY};
+use crate::scalar::Scalar;
+
+/// Window width of the table of G used by `mul2_vartime`
+const G_WIDTH: u32 = 5;
+
+/// Odd multiples G, 3G, ..., 15G for the wNAF of width `G_WIDTH`
+///
+/// They are hardcoded so verifying a signature doesn't rebuild them, and kept
+/// in affine coordinates so the additions use the cheaper mixed formulas
+const G_TABLE: [Pt; 1 << (G_WIDTH - 2)] = [
+    G,
+    // 3G
+    Pt::new(
+        fe!("f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9"),
+        fe!("388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672"),
+    ),
+    // 5G
+    Pt::new(
+        fe!("2f8bde4d1a07209355b4a7250a5c5128e88b84bddc619ab7cba8d569b240efe4"),
+        fe!("d8ac222636e5e3d6d4dba9dda6c9c426f788271bab0d6840dca87d3aa6ac62d6"),
+    ),
+    // 7G
+    Pt::new(
+        fe!("5cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc"),
+        fe!("6aebca40ba255960a3178d6d861a54dba813d0b813fde7b5a5082628087264da"),
+    ),
+    // 9G
+    Pt::new(
+        fe!("acd484e2f0c7f65309ad178a9f559abde09796974c57e714c35f110dfc27ccbe"),
+        fe!("cc338921b0a7d9fd64380971763b61e9add888a4375f8e0f05cc262ac64f9c37"),
+    ),
+    // 11G
+    Pt::new(
+        fe!("774ae7f858a9411e5ef4246b70c65aac5649980be5c17891bbec17895da008cb"),
+        fe!("d984a032eb6b5e190243dd56d7b7b365372db1e2dff9d6a8301d74c9c953c61b"),
+    ),
+    // 13G
+    Pt::new(
+        fe!("f28773c2d975288bc7d1d205c3748651b075fbc6610e58cddeeddf8f19405aa8"),
+        fe!("0ab0902e8d880a89758212eb65cdaf473a1a06da521fa91f29b5cb52db03ed81"),
+    ),
+    // 15G
+    Pt::new(
+        fe!("d7924d4f7d43ea965a465ae3095ff41131e5946f3c85f79e44adbcf8e27e080e"),
+        fe!("581e2872a86c72a683842ec228cc6defea40af2bd896d3a5c504dc9ff6a26b58"),
+    ),
+];
+
+/// Odd multiples of a base point for width-w NAF multiplications
+///
+/// The table holds P, 3P, 5P, ..., (2^(w-1) - 1)P. Building it costs a
+/// doubling and 2^(w-2) - 1 additions, which pays off when the same point is
+/// multiplied many times, e.g. a public key verifying signatures.
+/// The multiplications run in variable time and must only be used with public
+/// scalars.
+#[derive(Clone, Debug)]
+pub struct PointTable {
+    points: Vec<JacobianPt>,
+    width: u32,
+}
+
+impl PointTable {
+    /// Precompute the odd multiples of base for a window of width bits
+    ///
+    /// # Panics
+    ///
+    /// Panics if width is not in [2, 8], like `Scalar::to_wnaf`
+    pub fn precompute(base: &Pt, width: u32) -> Self {
+        assert!((2..=8).contains(&width));
+
+        let p = JacobianPt::from_affine(base);
+        let p2 = p.double();
+        let mut points = Vec::with_capacity(1 << (width - 2));
+
+        points.push(p);
+        for i in 1..(1 << (width - 2)) {
+            let next = points[i - 1].add(&p2);
+            points.push(next);
+        }
+
+        Self { points, width }
+    }
+
+    /// Calculate r + digit * P for an odd wNAF digit
+    fn add_digit(&self, r: &JacobianPt, digit: i8) -> JacobianPt {
+        let p = &self.points[(digit.unsigned_abs() >> 1) as usize];
+
+        if digit > 0 {
+            r.add(p)
+        } else {
+            r.add(&JacobianPt { y: p.y.negate(1).reduced(), ..*p })
+        }
+    }
+
+    /// Multiply the base point with k in variable time, k must be reduced % N
+    pub fn mul(&self, k: &Scalar) -> JacobianPt {
+        let mut r = JACOBIAN_INFINITY;
+
+        for &digit in k.to_wnaf(self.width).iter().rev() {
+            r = r.double();
+            if digit != 0 {
+                r = self.add_digit(&r, digit);
+            }
+        }
+        r
+    }
+
+    /// The base point P of the table
+    pub fn base(&self) -> &JacobianPt {
+        &self.points[0]
+    }
+}
+
+/// Calculate r + digit * G for an odd wNAF digit of width `G_WIDTH`
+fn add_g_digit(r: &JacobianPt, digit: i8) -> JacobianPt {
+    let p = &G_TABLE[(digit.unsigned_abs() >> 1) as usize];
+
+    if digit > 0 {
+        r.add_affine(p)
+    } else {
+        r.add_affine(&-*p)
+    }
+}
+
+/// Calculate u1 * G + u2 * Q in variable time, with Q given as a table
+///
+/// Both wNAF are walked together so the doublings are shared (Shamir's
+/// trick). The multiples of G come from a constant table. The table of Q can
+/// be kept to verify many signatures of the same key. u1 and u2 must be
+/// reduced % N and public
+pub fn mul2_vartime(u1: &Scalar, u2: &Scalar, q: &PointTable) -> JacobianPt {
+    let naf1 = u1.to_wnaf(G_WIDTH);
+    let naf2 = u2.to_wnaf(q.width);
+    let mut r = JACOBIAN_INFINITY;
+
+    for i in (0..naf1.len().max(naf2.len())).rev() {
+        r = r.double();
+        if let Some(&digit) = naf1.get(i).filter(|d| **d != 0) {
+            r = add_g_digit(&r, digit);
+        }
+        if let Some(&digit) = naf2.get(i).filter(|d| **d != 0) {
+            r = q.add_digit(&r, digit);
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecc::INFINITY;
+    use crate::scalar::N;
+
+    #[test]
+    fn it_multiplies_with_a_point_table() {
+        let p = G * Scalar::from_u64(0x2a);
+        let ks = [
+            Scalar::ZERO,
+            Scalar::ONE,
+            Scalar::from_u64(0xdeadbeef),
+            N - Scalar::ONE,
+            Scalar::from_bytes(&[0x5a; 32]),
+        ];
+
+        for width in 2..=8 {
+            let table = PointTable::precompute(&p, width);
+            assert_eq!(table.points.len(), 1 << (width - 2));
+
+            for k in ks.iter() {
+                assert_eq!(table.mul(k).to_affine().unwrap_or(INFINITY), p * k);
+            }
+        }
+    }
+
+    #[test]
+    fn it_matches_the_table_of_g() {
+        let table = PointTable::precompute(&G, G_WIDTH);
+
+        assert_eq!(table.base(), &JacobianPt::from_affine(&G));
+        assert_eq!(table.points.len(), G_TABLE.len());
+        for (p, expected) in table.points.iter().zip(G_TABLE.iter()) {
+            assert_eq!(p.to_affine().as_ref(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn it_computes_double_multiplications() {
+        let q = G * Scalar::from_bytes(&[0x17; 32]);
+        let table = PointTable::precompute(&q, 4);
+        let u1 = Scalar::from_bytes(&[0x5a; 32]);
+        let u2 = N - Scalar::from_u64(3);
+
+        assert_eq!(
+            mul2_vartime(&u1, &u2, &table).to_affine(),
+            Some(G * u1 + q * u2)
+        );
+        assert_eq!(
+            mul2_vartime(&u1, &Scalar::ZERO, &table).to_affine(),
+            Some(G * u1)
+        );
+        assert_eq!(
+            mul2_vartime(&Scalar::ZERO, &u2, &table).to_affine(),
+            Some(q * u2)
+        );
+
+        // u1 * G = -(u2 * Q)
+        let table = PointTable::precompute(&G, 3);
+        let r = mul2_vartime(&u1, &(N - u1), &table);
+        assert!(r.inf);
+    }
+}
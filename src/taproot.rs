@@ -0,0 +1,98 @@
+use crate::ecc::G;
+use crate::hmac::tagged_hash;
+use crate::key::XOnlyPublicKey;
+use crate::scalar::Scalar;
+
+/// Verify that a taproot output key commits to an internal key
+///
+/// Following BIP-341, the output key is Q = P + t * G with
+/// t = hashTapTweak(P || root) and root the merkle root of the script tree.
+/// A key path only output has no merkle root and t = hashTapTweak(P).
+#[must_use]
+pub fn verify_taproot_commitment(
+    internal_key: &XOnlyPublicKey,
+    merkle_root: Option<&[u8; 32]>,
+    output_key: &XOnlyPublicKey,
+) -> bool {
+    let mut buf = [0u8; 64];
+
+    buf[0..32].copy_from_slice(&internal_key.serialize());
+    let len = match merkle_root {
+        Some(root) => {
+            buf[32..64].copy_from_slice(root);
+            64
+        }
+        None => 32,
+    };
+
+    let t = Scalar::from_bytes(&tagged_hash(b"TapTweak", &buf[..len]));
+    if t.get_overflow() != 0 {
+        return false;
+    }
+
+    let mut q = internal_key.key + G * t;
+    if q.inf {
+        return false;
+    }
+    q.x.reduce();
+
+    q.x.to_bytes() == output_key.serialize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERNAL_KEY_1: [u8; 32] = [
+        0xd6, 0x88, 0x9c, 0xb0, 0x81, 0x03, 0x6e, 0x0f, 0xae, 0xfa, 0x3a, 0x35, 0x15, 0x7a, 0xd7,
+        0x10, 0x86, 0xb1, 0x23, 0xb2, 0xb1, 0x44, 0xb6, 0x49, 0x79, 0x8b, 0x49, 0x4c, 0x30, 0x0a,
+        0x96, 0x1d,
+    ];
+    const OUTPUT_KEY_1: [u8; 32] = [
+        0x53, 0xa1, 0xf6, 0xe4, 0x54, 0xdf, 0x1a, 0xa2, 0x77, 0x6a, 0x28, 0x14, 0xa7, 0x21, 0x37,
+        0x2d, 0x62, 0x58, 0x05, 0x0d, 0xe3, 0x30, 0xb3, 0xc6, 0xd1, 0x0e, 0xe8, 0xf4, 0xe0, 0xdd,
+        0xa3, 0x43,
+    ];
+    const INTERNAL_KEY_2: [u8; 32] = [
+        0x18, 0x77, 0x91, 0xb6, 0xf7, 0x12, 0xa8, 0xea, 0x41, 0xc8, 0xec, 0xdd, 0x0e, 0xe7, 0x7f,
+        0xab, 0x3e, 0x85, 0x26, 0x3b, 0x37, 0xe1, 0xec, 0x18, 0xa3, 0x65, 0x19, 0x26, 0xb3, 0xa6,
+        0xcf, 0x27,
+    ];
+    const MERKLE_ROOT_2: [u8; 32] = [
+        0x5b, 0x75, 0xad, 0xec, 0xf5, 0x35, 0x48, 0xf3, 0xec, 0x6a, 0xd7, 0xd7, 0x83, 0x83, 0xbf,
+        0x84, 0xcc, 0x57, 0xb5, 0x5a, 0x31, 0x27, 0xc7, 0x2b, 0x9a, 0x24, 0x81, 0x75, 0x2d, 0xd8,
+        0x8b, 0x21,
+    ];
+    const OUTPUT_KEY_2: [u8; 32] = [
+        0x14, 0x7c, 0x9c, 0x57, 0x13, 0x2f, 0x6e, 0x7e, 0xcd, 0xdb, 0xa9, 0x80, 0x0b, 0xb0, 0xc4,
+        0x44, 0x92, 0x51, 0xc9, 0x2a, 0x1e, 0x60, 0x37, 0x1e, 0xe7, 0x75, 0x57, 0xb6, 0x62, 0x0f,
+        0x3e, 0xa3,
+    ];
+
+    // BIP-341 wallet test vectors (scriptPubKey)
+    #[test]
+    fn it_verifies_key_path_commitment() {
+        let internal_key = XOnlyPublicKey::parse(&INTERNAL_KEY_1).unwrap();
+        let output_key = XOnlyPublicKey::parse(&OUTPUT_KEY_1).unwrap();
+
+        assert!(verify_taproot_commitment(&internal_key, None, &output_key));
+        assert!(!verify_taproot_commitment(
+            &internal_key,
+            Some(&MERKLE_ROOT_2),
+            &output_key
+        ));
+    }
+
+    #[test]
+    fn it_verifies_script_path_commitment() {
+        let internal_key = XOnlyPublicKey::parse(&INTERNAL_KEY_2).unwrap();
+        let output_key = XOnlyPublicKey::parse(&OUTPUT_KEY_2).unwrap();
+
+        assert!(verify_taproot_commitment(
+            &internal_key,
+            Some(&MERKLE_ROOT_2),
+            &output_key
+        ));
+        assert!(!verify_taproot_commitment(&internal_key, None, &output_key));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::ecc::Pt;
+use crate::hmac::tagged_hash;
+use crate::key::PublicKey;
+use crate::scalar::Scalar;
+
+const APPEND_TAG: &[u8] = b"estel_secp256k1/transcript/append";
+const CHALLENGE_TAG: &[u8] = b"estel_secp256k1/transcript/challenge";
+
+/// Fiat-Shamir transcript of a proof
+///
+/// The prover and the verifier append the same messages in the same order,
+/// and both derive the challenges from everything appended so far. Each
+/// message is hashed with its label and its length into the 32 bytes state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transcript {
+    state: [u8; 32],
+}
+
+impl Transcript {
+    /// Start a transcript for a protocol, domain must be unique to it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use estel_secp256k1::*;
+    ///
+    /// let mut t = Transcript::new(b"my protocol");
+    /// t.append_point(b"commitment", &G);
+    /// let c = t.challenge_scalar(b"c");
+    /// ```
+    pub fn new(domain: &[u8]) -> Self {
+        Self { state: tagged_hash(APPEND_TAG, domain) }
+    }
+
+    /// Append bytes, their length is hashed too so messages can't be shifted
+    pub fn append_bytes(&mut self, label: &[u8], b: &[u8]) {
+        let mut buf = self.framed(label, 4 + b.len());
+
+        buf.extend_from_slice(&(b.len() as u32).to_be_bytes());
+        buf.extend_from_slice(b);
+        self.state = tagged_hash(APPEND_TAG, &buf);
+    }
+
+    /// Append a point in its compressed form, 33 zeros for the point at infinity
+    pub fn append_point(&mut self, label: &[u8], p: &Pt) {
+        let sec = if p.inf {
+            [0u8; 33]
+        } else {
+            PublicKey::from_coords(p.x, p.y).serialize_sec_compressed()
+        };
+
+        self.append_bytes(label, &sec);
+    }
+
+    /// Append a scalar as 32 bytes in big endian
+    pub fn append_scalar(&mut self, label: &[u8], s: &Scalar) {
+        self.append_bytes(label, &s.to_bytes());
+    }
+
+    /// Derive a challenge % N from the transcript
+    ///
+    /// The challenge is added to the transcript, so the next challenges differ
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> Scalar {
+        let buf = self.framed(label, 0);
+
+        self.state = tagged_hash(CHALLENGE_TAG, &buf);
+        let mut c = Scalar::from_bytes(&self.state);
+        c.reduce(c.get_overflow());
+        c
+    }
+
+    /// state || len(label) || label, with room for extra bytes
+    fn framed(&self, label: &[u8], extra: usize) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(36 + label.len() + extra);
+
+        buf.extend_from_slice(&self.state);
+        buf.extend_from_slice(&(label.len() as u32).to_be_bytes());
+        buf.extend_from_slice(label);
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecc::{G, INFINITY};
+
+    #[test]
+    fn it_derives_challenges_from_the_transcript() {
+        let transcript = |msg: &[u8]| {
+            let mut t = Transcript::new(b"test");
+            t.append_point(b"p", &G);
+            t.append_scalar(b"s", &Scalar::from_u64(0x2a));
+            t.append_bytes(b"m", msg);
+            t
+        };
+
+        let mut t = transcript(b"hello");
+        let c1 = t.challenge_scalar(b"c");
+        let c2 = t.challenge_scalar(b"c");
+        assert_eq!(c1, transcript(b"hello").challenge_scalar(b"c"));
+        assert_ne!(c1, c2);
+        assert_eq!(c1.get_overflow(), 0);
+
+        assert_ne!(c1, transcript(b"hellO").challenge_scalar(b"c"));
+        assert_ne!(c1, transcript(b"hello").challenge_scalar(b"d"));
+        assert_ne!(c1, Transcript::new(b"test").challenge_scalar(b"c"));
+
+        // the label and the message are framed with their lengths
+        let mut a = Transcript::new(b"test");
+        a.append_bytes(b"ab", b"c");
+        let mut b = Transcript::new(b"test");
+        b.append_bytes(b"a", b"bc");
+        assert_ne!(a, b);
+
+        let mut a = Transcript::new(b"test");
+        a.append_point(b"p", &INFINITY);
+        let mut b = Transcript::new(b"test");
+        b.append_point(b"p", &G);
+        assert_ne!(a, b);
+    }
+}
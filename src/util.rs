@@ -0,0 +1,33 @@
+/// Compare two byte slices in constant time
+///
+/// All the bytes are compared, there is no early exit on the first difference.
+/// Only the lengths, which are considered public, are compared with a branch
+#[must_use]
+pub fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_compares_bytes_in_constant_time() {
+        let a = [0x42u8; 32];
+        let mut b = a;
+
+        assert!(ct_eq_bytes(&a, &b));
+        b[17] ^= 0x01;
+        assert!(!ct_eq_bytes(&a, &b));
+        assert!(!ct_eq_bytes(&a, &a[..31]));
+        assert!(ct_eq_bytes(&[], &[]));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::ecc::{is_in_group, Pt, G};
+use crate::hash_to_curve::hash_to_curve_tai;
+use crate::hmac::sha256;
+use crate::key::{PrivateKey, PublicKey};
+use crate::scalar::{negate_mod_n, Scalar};
+
+/// Suite string of ECVRF-SECP256K1-SHA256-TAI
+const SUITE: u8 = 0xfe;
+
+/// Proof of a VRF output, with gamma it forms pi = (gamma, c, s)
+///
+/// c is a 128 bits challenge and s is in [0, N)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VrfProof {
+    pub c: Scalar,
+    pub s: Scalar,
+}
+
+fn point_to_string(p: &Pt) -> [u8; 33] {
+    PublicKey::from_coords(p.x, p.y).serialize_sec_compressed()
+}
+
+/// H = encode_to_curve(PK_string, alpha)
+fn encode_to_curve(pk: &Pt, alpha: &[u8]) -> Pt {
+    let mut prefix = [0u8; 35];
+
+    prefix[0] = SUITE;
+    prefix[1] = 0x01;
+    prefix[2..35].copy_from_slice(&point_to_string(pk));
+
+    hash_to_curve_tai(&prefix, alpha)
+}
+
+/// c = Hash(suite || 0x02 || points || 0x00) truncated to 16 bytes
+fn challenge(points: &[&Pt; 5]) -> Scalar {
+    let mut buf = [0u8; 2 + 5 * 33 + 1];
+
+    buf[0] = SUITE;
+    buf[1] = 0x02;
+    for (i, p) in points.iter().enumerate() {
+        buf[2 + i * 33..35 + i * 33].copy_from_slice(&point_to_string(p));
+    }
+
+    let mut c = [0u8; 32];
+    c[16..32].copy_from_slice(&sha256(&buf)[0..16]);
+    Scalar::from_bytes(&c)
+}
+
+/// Calculate the VRF output beta from gamma, the cofactor is 1
+pub fn vrf_proof_to_hash(gamma: &Pt) -> [u8; 32] {
+    let mut buf = [0u8; 2 + 33 + 1];
+
+    buf[0] = SUITE;
+    buf[1] = 0x03;
+    buf[2..35].copy_from_slice(&point_to_string(gamma));
+
+    sha256(&buf)
+}
+
+/// Prove the VRF evaluation of alpha, the output is given by `vrf_proof_to_hash(gamma)`
+///
+/// This follows ECVRF of RFC 9381 with the TAI encoding, SHA-256 and the
+/// RFC 6979 nonce, using 0xfe as the secp256k1 suite string
+///
+/// # Example
+///
+/// ```
+/// use estel_secp256k1::*;
+///
+/// let secret = Scalar::from_bytes(&hash256("the force".as_bytes()));
+/// let pk = PublicKey::from_secret(&secret);
+/// let alpha = "May the force be with you".as_bytes();
+/// let (gamma, proof) = vrf_prove(&PrivateKey::new(secret), alpha);
+///
+/// assert_eq!(vrf_verify(&pk, alpha, &gamma, &proof), Some(vrf_proof_to_hash(&gamma)));
+/// ```
+pub fn vrf_prove(sk: &PrivateKey, alpha: &[u8]) -> (Pt, VrfProof) {
+    let x = sk.secret();
+    let y = G * x;
+    let h = encode_to_curve(&y, alpha);
+    let gamma = h * x;
+
+    let k = sk.calculate_k(&Scalar::from_bytes(&sha256(&point_to_string(&h))));
+    let c = challenge(&[&y, &h, &gamma, &(G * k), &(h * k)]);
+    let s = c.mul_add(x, &k);
+
+    (gamma, VrfProof { c, s })
+}
+
+/// Verify a VRF proof of alpha and return the output beta if it is valid
+#[must_use]
+pub fn vrf_verify(pk: &PublicKey, alpha: &[u8], gamma: &Pt, proof: &VrfProof) -> Option<[u8; 32]> {
+    if proof.s.get_overflow() != 0 || proof.c.get_overflow() != 0 || !is_in_group(gamma) {
+        return None;
+    }
+
+    let y = pk.key;
+    let h = encode_to_curve(&y, alpha);
+    let nc = negate_mod_n(&proof.c);
+    // U = s * G - c * Y, V = s * H - c * gamma
+    let u = G.mul_vartime(&proof.s) + y.mul_vartime(&nc);
+    let v = h.mul_vartime(&proof.s) + gamma.mul_vartime(&nc);
+    if u.inf || v.inf {
+        return None;
+    }
+
+    if challenge(&[&y, &h, gamma, &u, &v]) == proof.c {
+        Some(vrf_proof_to_hash(gamma))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use std::convert::TryInto;
+
+    #[test]
+    fn it_proves_a_known_vrf_output() {
+        let sk = PrivateKey::new(Scalar::from_u64(0x2a));
+        let (gamma, proof) = vrf_prove(&sk, b"alpha");
+        let beta: [u8; 32] =
+            hex::decode("86428c2286ed008ae471896d2394ba2baea8e5a8a85bd873c60dc0aaf112ec92")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        assert_eq!(
+            point_to_string(&gamma).to_vec(),
+            hex::decode("025c02688870d19a969949b4412915c7b15c0abb7be7703cc54020f71a097e8b14")
+                .unwrap()
+        );
+        assert_eq!(
+            proof.c,
+            Scalar::new(0, 0, 0x5e4a0ab36973f295, 0x92d501b862bede0b)
+        );
+        assert_eq!(
+            proof.s,
+            Scalar::new(
+                0x85baca0bca214b61,
+                0x139b49d850e9efc7,
+                0x854b0c5c35005591,
+                0x2680d105e2a00d86,
+            )
+        );
+        assert_eq!(vrf_proof_to_hash(&gamma), beta);
+    }
+
+    #[test]
+    fn it_proves_and_verifies_vrf() {
+        let secret = Scalar::from_u64(0x2a);
+        let sk = PrivateKey::new(secret);
+        let pk = PublicKey::from_secret(&secret);
+        let (gamma, proof) = vrf_prove(&sk, b"alpha");
+        let beta = vrf_proof_to_hash(&gamma);
+
+        assert_eq!(vrf_verify(&pk, b"alpha", &gamma, &proof), Some(beta));
+        assert_eq!(vrf_prove(&sk, b"alpha"), (gamma, proof));
+
+        assert_eq!(vrf_verify(&pk, b"beta", &gamma, &proof), None);
+        assert_eq!(vrf_verify(&pk, b"alpha", &G, &proof), None);
+        let other = PublicKey::from_secret(&Scalar::from_u64(0x2b));
+        assert_eq!(vrf_verify(&other, b"alpha", &gamma, &proof), None);
+        let bad = VrfProof { c: proof.c, s: proof.s + Scalar::from_u64(1) };
+        assert_eq!(vrf_verify(&pk, b"alpha", &gamma, &bad), None);
+    }
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "test-compat")]
+
+use estel_secp256k1::{hash256, PrivateKey, PublicKey, Scalar, Signature};
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::ecdsa::{Signature as KSignature, SigningKey, VerifyingKey};
+
+const SEEDS: [&str; 4] = ["n00b", "the force", "some password", "Hello World"];
+const MSGS: [&str; 3] = ["", "Hello World", "The greatest teacher failure is"];
+
+fn keypair(seed: &str) -> ([u8; 32], PrivateKey, PublicKey) {
+    let secret = hash256(seed.as_bytes());
+    let n = Scalar::from_bytes(&secret);
+
+    (secret, PrivateKey::new(n), PublicKey::from_secret(&n))
+}
+
+#[test]
+fn it_derives_the_same_public_keys() {
+    for seed in SEEDS {
+        let (secret, _, mut pk) = keypair(seed);
+        let sk = SigningKey::from_slice(&secret).unwrap();
+        let vk = sk.verifying_key();
+
+        assert_eq!(
+            vk.to_encoded_point(false).as_bytes(),
+            &pk.serialize_sec_uncompressed()[..],
+            "seed: {:?}",
+            seed
+        );
+        assert_eq!(
+            vk.to_encoded_point(true).as_bytes(),
+            &pk.serialize_sec_compressed()[..],
+            "seed: {:?}",
+            seed
+        );
+    }
+}
+
+#[test]
+fn it_verifies_k256_signatures() {
+    for seed in SEEDS {
+        let (secret, _, pk) = keypair(seed);
+        let sk = SigningKey::from_slice(&secret).unwrap();
+
+        for msg in MSGS {
+            let hash = hash256(msg.as_bytes());
+            let ksig: KSignature = sk.sign_prehash(&hash).unwrap();
+            let sig = Signature::parse_der(ksig.to_der().as_bytes()).unwrap();
+
+            assert!(
+                pk.verify(&Scalar::from_bytes(&hash), &sig),
+                "seed: {:?}, msg: {:?}",
+                seed,
+                msg
+            );
+        }
+    }
+}
+
+#[test]
+fn it_creates_signatures_verified_by_k256() {
+    for seed in SEEDS {
+        let (secret, pvk, mut pk) = keypair(seed);
+        let vk = VerifyingKey::from_sec1_bytes(&pk.serialize_sec_compressed()).unwrap();
+        let sk = SigningKey::from_slice(&secret).unwrap();
+
+        for msg in MSGS {
+            let hash = hash256(msg.as_bytes());
+            let (der, len) = pvk.sign(&Scalar::from_bytes(&hash)).serialize_der();
+            // k256 only accepts signatures with a low s
+            let sig = KSignature::from_der(&der[..len]).unwrap();
+            let sig = sig.normalize_s().unwrap_or(sig);
+
+            assert!(
+                vk.verify_prehash(&hash, &sig).is_ok(),
+                "seed: {:?}, msg: {:?}",
+                seed,
+                msg
+            );
+
+            // both implementations use RFC6979 for the nonce
+            let ksig: KSignature = sk.sign_prehash(&hash).unwrap();
+            assert_eq!(sig, ksig, "seed: {:?}, msg: {:?}", seed, msg);
+        }
+    }
+}
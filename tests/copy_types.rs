@@ -0,0 +1,59 @@
+#![cfg(feature = "secp256k1")]
+
+use estel_secp256k1::*;
+
+fn take<T: Copy>(x: T) -> T {
+    x
+}
+
+fn take_clone<T: Clone>(x: &T) -> T {
+    x.clone()
+}
+
+#[test]
+fn it_passes_small_types_by_copy() {
+    let k = Scalar::from_u64(7);
+    let p = G * k;
+    let pk = PublicKey::from_secret(&k);
+    let jp = JacobianPt::from_affine(&p);
+
+    // each value is still usable after being passed by value
+    assert_eq!(take(k), k);
+    assert_eq!(take(BETA), BETA);
+    assert_eq!(take(p), p);
+    assert_eq!(take(jp), jp);
+    assert_eq!(take(SilentPoint(jp)).0, jp);
+    assert_eq!(take(pk), pk);
+    assert_eq!(
+        take(XOnlyPublicKey::from_public_key(&pk)).serialize()[..],
+        p.x.to_bytes()
+    );
+    assert_eq!(take(PaddedScalar::from_scalar(&k)).to_scalar(), k);
+    assert_eq!(take(secp256k1_info()), secp256k1_info());
+    assert_eq!(take(Error::InvalidPoint), Error::InvalidPoint);
+    assert_eq!(take(ModInvStrategy::Lehmer), ModInvStrategy::Lehmer);
+    take(RFC6979NonceFunction);
+}
+
+#[test]
+fn it_clones_types_holding_secrets_or_state() {
+    let key = PrivateKey::new(Scalar::from_u64(7));
+    let msg = Scalar::from_bytes(&sha256(b"clone"));
+
+    let a = take_clone(&key).sign(&msg);
+    let b = key.sign(&msg);
+    assert_eq!(a, b);
+
+    let prf = Prf::new(key);
+    assert_eq!(take_clone(&prf).eval(b"path"), prf.eval(b"path"));
+
+    let mut drbg = HmacDrbg::new(b"entropy", b"nonce", b"");
+    let mut other = take_clone(&drbg);
+    let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+    drbg.generate(&mut x);
+    other.generate(&mut y);
+    assert_eq!(x, y);
+
+    let t = Transcript::new(b"clone");
+    assert_eq!(take_clone(&t), t);
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "secp256k1")]
+
 use estel_secp256k1::generate_keypair_from_seed;
 
 #[test]
@@ -7,7 +9,7 @@ fn it_verify_a_signature_from_buffer() {
     let msg = "Hello World".as_bytes();
 
     // create a signature
-    let sig = privkey.sign_buffer(&msg);
+    let sig = privkey.sign_buffer(msg);
     // verify signature
-    assert!(pubkey.verify_buffer(&msg, &sig));
+    assert!(pubkey.verify_buffer(msg, &sig));
 }
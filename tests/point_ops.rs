@@ -0,0 +1,22 @@
+#![cfg(feature = "secp256k1")]
+
+use estel_secp256k1::{Scalar, G, INFINITY};
+
+#[test]
+fn it_adds_points_with_operators() {
+    assert_eq!(G + G + G, Scalar::from_u64(3) * G);
+    assert_eq!(-G + G, INFINITY);
+    assert_eq!(G - G, INFINITY);
+    assert_eq!(G + INFINITY, G);
+    assert_eq!(-INFINITY, INFINITY);
+}
+
+#[test]
+fn it_subtracts_points_with_operators() {
+    let p = G * Scalar::from_u64(5);
+    let q = G * Scalar::from_u64(3);
+
+    assert_eq!(p - q, G + G);
+    assert_eq!(q - p, -(G + G));
+    assert_eq!(-(-p), p);
+}
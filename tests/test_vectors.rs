@@ -0,0 +1,75 @@
+#![cfg(feature = "secp256k1")]
+
+use estel_secp256k1::{PublicKey, Scalar, Signature};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// N / 2, bitcoin rejects signatures with s greater than this value
+const N_HALF: Scalar = Scalar::new(
+    0x7fffffffffffffff,
+    0xffffffffffffffff,
+    0x5d576e7357a4501d,
+    0xdfe92f46681b20a0,
+);
+
+fn from_hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..(i + 2)], 16).unwrap())
+        .collect()
+}
+
+fn load(name: &str) -> Value {
+    let path = format!("{}/tests/vectors/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let data = std::fs::read_to_string(path).unwrap();
+
+    serde_json::from_str(&data).unwrap()
+}
+
+/// Run a wycheproof ECDSA file, returning the ids of the failing tests
+///
+/// Invalid tests must fail to parse or to verify, valid tests must verify.
+/// With low_s, signatures with s > N / 2 are invalid like in bitcoin.
+fn run_ecdsa_vectors(name: &str, low_s: bool) -> Vec<u64> {
+    let vectors = load(name);
+    let mut failures = Vec::new();
+    let mut count = 0;
+
+    for group in vectors["testGroups"].as_array().unwrap() {
+        let sec = from_hex(group["publicKey"]["uncompressed"].as_str().unwrap());
+        let pk = PublicKey::parse_sec(&sec).unwrap();
+
+        for test in group["tests"].as_array().unwrap() {
+            let msg = from_hex(test["msg"].as_str().unwrap());
+            let der = from_hex(test["sig"].as_str().unwrap());
+            let z = Scalar::from_bytes(&Sha256::digest(&msg).into());
+            let expected = test["result"].as_str().unwrap() == "valid";
+
+            let res = match Signature::parse_der(&der) {
+                Ok(sig) => (!low_s || sig.s <= N_HALF) && pk.verify(&z, &sig),
+                Err(_) => false,
+            };
+            if res != expected {
+                failures.push(test["tcId"].as_u64().unwrap());
+            }
+            count += 1;
+        }
+    }
+    assert_eq!(count, vectors["numberOfTests"].as_u64().unwrap());
+
+    failures
+}
+
+#[test]
+fn it_runs_bitcoin_ecdsa_vectors() {
+    let failures = run_ecdsa_vectors("ecdsa_secp256k1_sha256_bitcoin_test.json", true);
+
+    assert!(failures.is_empty(), "failing tests: {:?}", failures);
+}
+
+#[test]
+fn it_runs_wycheproof_ecdsa_vectors() {
+    let failures = run_ecdsa_vectors("ecdsa_secp256k1_sha256_test.json", false);
+
+    assert!(failures.is_empty(), "failing tests: {:?}", failures);
+}